@@ -1,29 +1,27 @@
 //! LeetCode 90: Subsets II
 //! Generate all unique subsets from a list that may contain duplicates
 
+use rusty_combinatorial::combinations::multiset::all_submultisets;
+
 fn subsets_with_dup(mut nums: Vec<i32>) -> Vec<Vec<i32>> {
     nums.sort();
-    let mut result = vec![];
-    let mut current = vec![];
-
-    fn backtrack(
-        start: usize,
-        nums: &[i32],
-        current: &mut Vec<i32>,
-        result: &mut Vec<Vec<i32>>,
-    ) {
-        result.push(current.clone());
-        for i in start..nums.len() {
-            if i > start && nums[i] == nums[i - 1] {
-                continue;
-            }
-            current.push(nums[i]);
-            backtrack(i + 1, nums, current, result);
-            current.pop();
+    let mut values: Vec<i32> = vec![];
+    let mut mult: Vec<usize> = vec![];
+    for &x in &nums {
+        if values.last() == Some(&x) {
+            *mult.last_mut().unwrap() += 1;
+        } else {
+            values.push(x);
+            mult.push(1);
         }
     }
 
-    backtrack(0, &nums, &mut current, &mut result);
+    let mut result: Vec<Vec<i32>> = (0..=nums.len())
+        .flat_map(|k| all_submultisets(&mult, k))
+        .map(|sub| sub.into_iter().map(|t| values[t]).collect())
+        .collect();
+    // submultisets come grouped by size; list them lexicographically instead
+    result.sort();
     result
 }
 
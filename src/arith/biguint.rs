@@ -0,0 +1,348 @@
+//! # Utility: Arithmetic — Arbitrary-Precision Unsigned Integers
+//!
+//! A small, dependency-free `BigUint` sufficient for ranking and counting
+//! combinatorial objects whose counts outgrow `u128` (e.g. `50!`).
+//!
+//! ## Representation
+//!
+//! - Little-endian base-2⁶⁴ limbs, normalised so the top limb is nonzero.
+//! - Zero is the empty limb vector.
+//!
+//! ## Complexity
+//!
+//! - Addition / subtraction: O(m) for m limbs
+//! - Multiplication / division by a machine word: O(m)
+//! - Full multiplication: O(m₁·m₂) (schoolbook)
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+use rand::Rng;
+
+/// An arbitrary-precision unsigned integer.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::arith::biguint::BigUint;
+/// let f25 = (1..=25u64).fold(BigUint::from(1u64), |acc, i| acc.mul_small(i));
+/// assert_eq!(f25.to_string(), "15511210043330985984000000");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    /// The value `0`.
+    pub fn zero() -> Self {
+        BigUint { limbs: Vec::new() }
+    }
+
+    /// The value `1`.
+    pub fn one() -> Self {
+        BigUint { limbs: vec![1] }
+    }
+
+    /// `true` if the value is `0`.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Number of significant bits (`0` for zero).
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(&top) => self.limbs.len() * 64 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Convert to `u64` if the value fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0]),
+            _ => None,
+        }
+    }
+
+    /// Convert to `u128` if the value fits.
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u128),
+            2 => Some(self.limbs[0] as u128 | (self.limbs[1] as u128) << 64),
+            _ => None,
+        }
+    }
+
+    /// Return `self · m`.
+    pub fn mul_small(&self, m: u64) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u128;
+        for &x in &self.limbs {
+            let t = x as u128 * m as u128 + carry;
+            limbs.push(t as u64);
+            carry = t >> 64;
+        }
+        limbs.push(carry as u64);
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Return `self + a`.
+    pub fn add_small(&self, a: u64) -> BigUint {
+        self + &BigUint::from(a)
+    }
+
+    /// Return `(self / d, self % d)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d == 0`.
+    pub fn div_rem_small(&self, d: u64) -> (BigUint, u64) {
+        assert!(d != 0, "division by zero");
+        let mut q = vec![0u64; self.limbs.len()];
+        let mut rem = 0u128;
+        for i in (0..self.limbs.len()).rev() {
+            let cur = rem << 64 | self.limbs[i] as u128;
+            q[i] = (cur / d as u128) as u64;
+            rem = cur % d as u128;
+        }
+        (BigUint::from_limbs(q), rem as u64)
+    }
+
+    /// Return `self − other`, or `None` if the result would be negative.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = self.limbs.clone();
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let rhs = other.limbs.get(i).copied().unwrap_or(0);
+            let (t, b1) = limb.overflowing_sub(rhs);
+            let (t, b2) = t.overflowing_sub(borrow as u64);
+            *limb = t;
+            borrow = b1 || b2;
+        }
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    /// Uniform random value in `0..bound`, by rejection on `bound.bits()`
+    /// random bits (fewer than two draws on average).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is zero.
+    pub fn random_below<R: Rng + ?Sized>(bound: &BigUint, rng: &mut R) -> BigUint {
+        assert!(!bound.is_zero(), "empty range");
+        let bits = bound.bits();
+        let top_mask = u64::MAX >> ((64 - bits % 64) % 64);
+        loop {
+            let mut limbs: Vec<u64> = (0..bound.limbs.len()).map(|_| rng.next_u64()).collect();
+            *limbs.last_mut().unwrap() &= top_mask;
+            let x = BigUint::from_limbs(limbs);
+            if x < *bound {
+                return x;
+            }
+        }
+    }
+
+    fn from_limbs(mut limbs: Vec<u64>) -> BigUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(x: u64) -> Self {
+        BigUint::from_limbs(vec![x])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(x: u128) -> Self {
+        BigUint::from_limbs(vec![x as u64, (x >> 64) as u64])
+    }
+}
+
+impl From<usize> for BigUint {
+    fn from(x: usize) -> Self {
+        BigUint::from(x as u64)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = false;
+        for (i, &x) in long.iter().enumerate() {
+            let y = short.get(i).copied().unwrap_or(0);
+            let (t, c1) = x.overflowing_add(y);
+            let (t, c2) = t.overflowing_add(carry as u64);
+            limbs.push(t);
+            carry = c1 || c2;
+        }
+        limbs.push(carry as u64);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        &self + &other
+    }
+}
+
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    /// # Panics
+    ///
+    /// Panics if `other > self`.
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other).expect("BigUint subtraction underflow")
+    }
+}
+
+impl Sub for BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: BigUint) -> BigUint {
+        &self - &other
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &x) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &y) in other.limbs.iter().enumerate() {
+                let t = x as u128 * y as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        &self * &other
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000; // 10¹⁹
+        if self.is_zero() {
+            return f.pad("0");
+        }
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (q, r) = rest.div_rem_small(CHUNK);
+            chunks.push(r);
+            rest = q;
+        }
+        let mut s = chunks.pop().unwrap().to_string();
+        for c in chunks.iter().rev() {
+            s.push_str(&format!("{:019}", c));
+        }
+        f.pad(&s)
+    }
+}
+
+/// Error returned when parsing a [`BigUint`] from a decimal string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid decimal digit string")
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseBigUintError);
+        }
+        let mut value = BigUint::zero();
+        for c in s.chars() {
+            let d = c.to_digit(10).ok_or(ParseBigUintError)?;
+            value = value.mul_small(10).add_small(d as u64);
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_biguint_roundtrip_u128() {
+        let x = u128::MAX - 12345;
+        let b = BigUint::from(x);
+        assert_eq!(b.to_u128(), Some(x));
+        assert_eq!(b.to_string(), x.to_string());
+        assert_eq!(b.to_string().parse::<BigUint>(), Ok(b.clone()));
+        let (q, r) = b.div_rem_small(97);
+        assert_eq!(q.to_u128(), Some(x / 97));
+        assert_eq!(r as u128, x % 97);
+    }
+
+    #[test]
+    fn test_biguint_arithmetic() {
+        let a: BigUint = "123456789012345678901234567890".parse().unwrap();
+        let b: BigUint = "987654321098765432109876543210".parse().unwrap();
+        assert_eq!((&a + &b).to_string(), "1111111110111111111011111111100");
+        assert_eq!((&b - &a).to_string(), "864197532086419753208641975320");
+        assert_eq!(
+            (&a * &b).to_string(),
+            "121932631137021795226185032733622923332237463801111263526900"
+        );
+        assert!(a < b);
+        assert_eq!(a.checked_sub(&b), None);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod biguint;
+pub mod rank_int;
@@ -0,0 +1,186 @@
+//! # Utility: Arithmetic — Rank Integer Types
+//!
+//! Ranking functions index combinatorial objects by integers that quickly
+//! outgrow `usize` (`21!` already exceeds `u64`). The [`RankInt`] trait lets the
+//! same ranking code run over `usize`, `u64`, `u128` or [`BigUint`], with every
+//! operation checked so that overflow surfaces as [`RankError::Overflow`]
+//! instead of a silently wrapped rank.
+
+use std::fmt;
+
+use super::biguint::BigUint;
+
+/// Errors produced by checked ranking and unranking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RankError {
+    /// The rank (or an intermediate count) does not fit in the rank type.
+    Overflow,
+    /// The rank is not smaller than the number of objects being indexed.
+    OutOfRange,
+    /// The object to rank is not valid for the given parameters.
+    InvalidInput,
+}
+
+impl fmt::Display for RankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RankError::Overflow => f.write_str("rank does not fit in the rank type"),
+            RankError::OutOfRange => f.write_str("rank is out of range"),
+            RankError::InvalidInput => f.write_str("object is not valid for ranking"),
+        }
+    }
+}
+
+impl std::error::Error for RankError {}
+
+/// An unsigned integer type usable as a rank.
+///
+/// All arithmetic is checked: `None` means the result is not representable.
+pub trait RankInt: Clone + Ord + fmt::Debug + fmt::Display {
+    /// The value `0`.
+    fn zero() -> Self;
+    /// Convert from `usize`, or `None` if it does not fit.
+    fn from_usize(x: usize) -> Option<Self>;
+    /// Convert to `usize`, or `None` if it does not fit.
+    fn to_usize(&self) -> Option<usize>;
+    /// `self + other`.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    /// `self − other`, `None` if negative.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    /// `self · m` for a machine-word multiplier.
+    fn checked_mul_usize(&self, m: usize) -> Option<Self>;
+    /// `(self / d, self % d)` for a nonzero machine-word divisor.
+    fn div_rem_usize(&self, d: usize) -> (Self, usize);
+
+    /// `true` if the value is `0`.
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    /// `self + a` for a machine-word addend.
+    fn checked_add_usize(&self, a: usize) -> Option<Self> {
+        self.checked_add(&Self::from_usize(a)?)
+    }
+}
+
+macro_rules! impl_rank_int {
+    ($($t:ty),*) => {$(
+        impl RankInt for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn from_usize(x: usize) -> Option<Self> {
+                x.try_into().ok()
+            }
+
+            fn to_usize(&self) -> Option<usize> {
+                (*self).try_into().ok()
+            }
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *other)
+            }
+
+            fn checked_sub(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_sub(*self, *other)
+            }
+
+            fn checked_mul_usize(&self, m: usize) -> Option<Self> {
+                <$t>::checked_mul(*self, m as $t)
+            }
+
+            fn div_rem_usize(&self, d: usize) -> (Self, usize) {
+                let d = d as $t;
+                (*self / d, (*self % d) as usize)
+            }
+        }
+    )*};
+}
+
+impl_rank_int!(usize, u64, u128);
+
+impl RankInt for BigUint {
+    fn zero() -> Self {
+        BigUint::zero()
+    }
+
+    fn from_usize(x: usize) -> Option<Self> {
+        Some(BigUint::from(x))
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        self.to_u64()?.try_into().ok()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        BigUint::checked_sub(self, other)
+    }
+
+    fn checked_mul_usize(&self, m: usize) -> Option<Self> {
+        Some(self.mul_small(m as u64))
+    }
+
+    fn div_rem_usize(&self, d: usize) -> (Self, usize) {
+        let (q, r) = self.div_rem_small(d as u64);
+        (q, r as usize)
+    }
+
+    fn is_zero(&self) -> bool {
+        BigUint::is_zero(self)
+    }
+}
+
+/// Locate `rank` in a run of consecutive blocks of objects.
+///
+/// Skips every block that lies wholly below `rank`, subtracting its size, and
+/// returns the index of the block that contains `rank`, leaving `rank` as the
+/// offset into it. Unranking walks count tables whose larger entries may not
+/// fit in `R`; such a `None` block holds more objects than any `R` can index,
+/// so it always contains the rank. Returns `None` if the blocks run out first.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::arith::rank_int::find_block;
+/// let mut rank = 7usize;
+/// assert_eq!(find_block(&mut rank, [Some(&3), Some(&2), None]), Some(2));
+/// assert_eq!(rank, 2);
+/// ```
+pub fn find_block<'a, R: RankInt + 'a>(
+    rank: &mut R,
+    blocks: impl IntoIterator<Item = Option<&'a R>>,
+) -> Option<usize> {
+    for (i, block) in blocks.into_iter().enumerate() {
+        match block.and_then(|b| rank.checked_sub(b)) {
+            Some(rest) => *rank = rest,
+            None => return Some(i),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_rank_int_checks_overflow() {
+        assert_eq!(RankInt::checked_mul_usize(&u64::MAX, 2), None);
+        assert_eq!(RankInt::checked_add_usize(&7u128, 5), Some(12));
+        assert_eq!(RankInt::div_rem_usize(&17u64, 5), (3, 2));
+        assert_eq!(<u64 as RankInt>::checked_sub(&3, &4), None);
+    }
+
+    #[test]
+    fn test_biguint_rank_int() {
+        let x = BigUint::from(u64::MAX).checked_mul_usize(4).unwrap();
+        assert_eq!(x.to_usize(), None);
+        let (q, r) = x.div_rem_usize(4);
+        assert_eq!((q.to_u64(), r), (Some(u64::MAX), 0));
+    }
+}
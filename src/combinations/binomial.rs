@@ -0,0 +1,199 @@
+//! # Chapter 5: Combinations — Binomial Coefficients
+//!
+//! Overflow-aware binomial coefficients `C(n, k)` and a precomputed Pascal
+//! table for code that needs many of them.
+//!
+//! ## Algorithm Description
+//!
+//! **Multiplicative formula**: with `k ≤ n - k`, build `C(n, i+1)` from
+//!   `C(n, i)` as `C(n, i) · (n-i) / (i+1)`. Dividing out `g = gcd(C(n, i), i+1)`
+//!   first gives `(C(n, i)/g) · ((n-i) / ((i+1)/g))`, where both divisions are
+//!   exact, so every intermediate value is at most the final result: the
+//!   computation overflows only if `C(n, k)` itself does.
+//! **Pascal table**: `C(n, k) = C(n-1, k-1) + C(n-1, k)`, with entries that
+//!   overflow recorded as `None`.
+//!
+//! ## Complexity
+//!
+//! - Single coefficient: O(k) word operations (O(k·m) for `m`-limb bignums)
+//! - Table: O(n·k) to build, O(1) per lookup
+//!
+//! ## Sharing the Table
+//!
+//! The `_with` forms of lex combination ranking
+//! ([`rank_comb_with`](super::ranking::rank_comb_with),
+//! [`unrank_comb_with`](super::ranking::unrank_comb_with)) read every binomial
+//! from a [`BinomialTable`] the caller builds once, while the plain forms
+//! compute each binomial on the fly in O(k). Integer-partition ranking needs
+//! the counts `p(i, j)` rather than binomials, so it shares
+//! [`checked_bounded_partition_table`](crate::partitions::count::checked_bounded_partition_table)
+//! instead.
+
+use crate::arith::biguint::BigUint;
+
+/// `C(n, k)` as `u128`, or `None` if it does not fit.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::binomial::binom_u128;
+/// assert_eq!(binom_u128(100, 50), Some(100891344545564193334812497256));
+/// assert_eq!(binom_u128(200, 100), None);
+/// ```
+pub fn binom_u128(n: usize, k: usize) -> Option<u128> {
+    fn gcd(a: u128, b: u128) -> u128 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut res: u128 = 1;
+    for i in 0..k as u128 {
+        let g = gcd(res, i + 1);
+        let top = (n as u128 - i) / ((i + 1) / g);
+        res = (res / g).checked_mul(top)?;
+    }
+    Some(res)
+}
+
+/// `C(n, k)`, or `None` if it does not fit in `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::binomial::checked_binom;
+/// assert_eq!(checked_binom(66, 33), Some(7219428434016265740));
+/// assert_eq!(checked_binom(68, 34), None);
+/// ```
+pub fn checked_binom(n: usize, k: usize) -> Option<usize> {
+    binom_u128(n, k)?.try_into().ok()
+}
+
+/// `C(n, k)`, clamped to `usize::MAX` on overflow.
+pub fn saturating_binom(n: usize, k: usize) -> usize {
+    checked_binom(n, k).unwrap_or(usize::MAX)
+}
+
+/// `C(n, k)` as an arbitrary-precision integer.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::binomial::binom_big;
+/// assert_eq!(
+///     binom_big(200, 100).to_string(),
+///     "90548514656103281165404177077484163874504589675413336841320"
+/// );
+/// ```
+pub fn binom_big(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    let k = k.min(n - k);
+    let mut res = BigUint::one();
+    for i in 0..k {
+        res = res.mul_small((n - i) as u64).div_rem_small(i as u64 + 1).0;
+    }
+    res
+}
+
+/// A precomputed Pascal triangle of `C(n, k)` for `n ≤ n_max`, `k ≤ k_max`.
+///
+/// Build it once and share it between ranking calls instead of recomputing
+/// binomials per call.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::binomial::BinomialTable;
+/// let t = BinomialTable::new(70, 35);
+/// assert_eq!(t.binom(10, 3), 120);
+/// assert_eq!(t.binom(3, 10), 0);
+/// assert_eq!(t.get(68, 34), None); // overflows usize
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinomialTable {
+    k_max: usize,
+    rows: Vec<Vec<Option<usize>>>,
+}
+
+impl BinomialTable {
+    /// Build the table for `0 ≤ n ≤ n_max` and `0 ≤ k ≤ k_max`.
+    pub fn new(n_max: usize, k_max: usize) -> Self {
+        let mut rows: Vec<Vec<Option<usize>>> = Vec::with_capacity(n_max + 1);
+        for n in 0..=n_max {
+            let width = n.min(k_max) + 1;
+            let mut row = vec![Some(1); width];
+            for k in 1..width {
+                let prev = &rows[n - 1];
+                let right = prev.get(k).copied().unwrap_or(Some(0));
+                row[k] = match (prev[k - 1], right) {
+                    (Some(a), Some(b)) => a.checked_add(b),
+                    _ => None,
+                };
+            }
+            rows.push(row);
+        }
+        BinomialTable { k_max, rows }
+    }
+
+    /// Largest `n` covered by the table.
+    pub fn n_max(&self) -> usize {
+        self.rows.len() - 1
+    }
+
+    /// Largest `k` covered by the table.
+    pub fn k_max(&self) -> usize {
+        self.k_max
+    }
+
+    /// `C(n, k)`, or `None` if it overflows `usize`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > n_max`, or if `k > k_max` while `k ≤ n`.
+    pub fn get(&self, n: usize, k: usize) -> Option<usize> {
+        if k > n {
+            return Some(0);
+        }
+        assert!(k <= self.k_max, "k = {} exceeds table bound {}", k, self.k_max);
+        self.rows[n][k]
+    }
+
+    /// `C(n, k)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry is outside the table or overflows `usize`.
+    pub fn binom(&self, n: usize, k: usize) -> usize {
+        self.get(n, k).expect("binomial coefficient overflows usize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants_agree() {
+        let table = BinomialTable::new(80, 80);
+        for n in 0..80 {
+            for k in 0..=n + 1 {
+                let exact = binom_big(n, k);
+                assert_eq!(binom_u128(n, k), exact.to_u128());
+                assert_eq!(checked_binom(n, k).map(|c| c as u64), exact.to_u64());
+                assert_eq!(table.get(n, k).map(|c| c as u64), exact.to_u64());
+            }
+        }
+        assert_eq!(saturating_binom(100, 50), usize::MAX);
+    }
+
+    #[test]
+    fn test_no_spurious_overflow() {
+        // res · (n - i) alone would overflow long before C(62, 31) does
+        assert_eq!(checked_binom(62, 31), Some(465428353255261088));
+        assert_eq!(checked_binom(usize::MAX, 1), Some(usize::MAX));
+        assert_eq!(checked_binom(usize::MAX, usize::MAX - 1), Some(usize::MAX));
+    }
+}
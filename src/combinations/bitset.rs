@@ -0,0 +1,569 @@
+//! # Chapter 5: Combinations — Bitmask Subsets
+//!
+//! Represent a subset of `0..n` as an integer whose bit `i` is set iff `i` is
+//! a member. Enumeration then reduces to a few arithmetic tricks on machine
+//! words. The [`BitMask`] trait covers `u64` and `u128`; the `*_words`
+//! functions and iterators handle sets wider than 128 bits stored as `&[u64]`
+//! (bit `i` lives in word `i / 64`).
+//!
+//! ## Algorithm Description
+//!
+//! **Gosper's hack**: the next larger integer with the same popcount is
+//!   `((r ^ x) >> 2) / c | r` with `c = x & -x` and `r = x + c`. Masks come
+//!   out in increasing numeric order, i.e. colex order of the index vectors.
+//! **Subsets of a mask, increasing**: `s ← ((s | !m) + 1) & m` carries through
+//!   the bits outside `m`.
+//! **Submasks, decreasing**: `s ← (s − 1) & m`, down to `0`.
+//! **Multi-word**: the same two steps, with the carry or borrow passed from
+//!   each word to the next.
+//!
+//! ## Complexity
+//!
+//! - Each step: O(1) word operations (O(n / 64) for multi-word sets, O(1)
+//!   amortized for multi-word subsets and submasks)
+//! - Conversions: O(popcount) for masks, O(n / 64 + popcount) for words
+
+use std::fmt::Debug;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+/// An unsigned machine integer used as a bitset.
+pub trait BitMask:
+    Copy
+    + Eq
+    + Ord
+    + Debug
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    /// Number of bits in the mask.
+    const BITS: u32;
+    /// The empty set.
+    const ZERO: Self;
+    /// The set `{0}`.
+    const ONE: Self;
+
+    /// `self + other`, `None` on overflow.
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// `self − other` modulo `2^BITS`.
+    fn wrapping_sub(self, other: Self) -> Self;
+    /// `−self` modulo `2^BITS`.
+    fn wrapping_neg(self) -> Self;
+    /// `self / other`.
+    fn div(self, other: Self) -> Self;
+    /// Number of set bits.
+    fn count_ones(self) -> u32;
+    /// Index of the lowest set bit (`BITS` for zero).
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! impl_bit_mask {
+    ($($t:ty),*) => {$(
+        impl BitMask for $t {
+            const BITS: u32 = <$t>::BITS;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn wrapping_sub(self, other: Self) -> Self {
+                <$t>::wrapping_sub(self, other)
+            }
+
+            fn wrapping_neg(self) -> Self {
+                <$t>::wrapping_neg(self)
+            }
+
+            fn div(self, other: Self) -> Self {
+                self / other
+            }
+
+            fn count_ones(self) -> u32 {
+                <$t>::count_ones(self)
+            }
+
+            fn trailing_zeros(self) -> u32 {
+                <$t>::trailing_zeros(self)
+            }
+        }
+    )*};
+}
+
+impl_bit_mask!(u64, u128);
+
+/// The mask `{0, 1, …, k-1}`.
+///
+/// # Panics
+///
+/// Panics if `k > M::BITS`.
+pub fn low_bits<M: BitMask>(k: usize) -> M {
+    assert!(k <= M::BITS as usize, "{} bits do not fit in a {}-bit mask", k, M::BITS);
+    if k == M::BITS as usize { !M::ZERO } else { (M::ONE << k as u32).wrapping_sub(M::ONE) }
+}
+
+/// Convert a sorted index vector (as produced by `combinations::lex`) to a mask.
+///
+/// # Panics
+///
+/// Panics if an index is `≥ M::BITS`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::indices_to_mask;
+/// assert_eq!(indices_to_mask::<u64>(&[0, 2, 5]), 0b100101);
+/// ```
+pub fn indices_to_mask<M: BitMask>(indices: &[usize]) -> M {
+    indices.iter().fold(M::ZERO, |m, &i| {
+        assert!(i < M::BITS as usize, "index {} does not fit in a {}-bit mask", i, M::BITS);
+        m | (M::ONE << i as u32)
+    })
+}
+
+/// The members of `mask` in increasing order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::mask_to_indices;
+/// assert_eq!(mask_to_indices(0b100101u64), vec![0, 2, 5]);
+/// ```
+pub fn mask_to_indices<M: BitMask>(mut mask: M) -> Vec<usize> {
+    let mut out = Vec::with_capacity(mask.count_ones() as usize);
+    while mask != M::ZERO {
+        out.push(mask.trailing_zeros() as usize);
+        mask = mask & mask.wrapping_sub(M::ONE);
+    }
+    out
+}
+
+/// The next larger mask with the same number of bits, if it stays below `2^n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::next_same_popcount;
+/// assert_eq!(next_same_popcount(0b0111u64, 4), Some(0b1011));
+/// assert_eq!(next_same_popcount(0b1110u64, 4), None);
+/// ```
+pub fn next_same_popcount<M: BitMask>(x: M, n: usize) -> Option<M> {
+    if x == M::ZERO {
+        return None;
+    }
+    let c = x & x.wrapping_neg();
+    let r = x.checked_add(c)?;
+    if n < M::BITS as usize && r >> n as u32 != M::ZERO {
+        return None;
+    }
+    Some(((r ^ x) >> 2).div(c) | r)
+}
+
+/// Iterator over the k-subsets of `0..n` as masks, in increasing numeric
+/// (colex) order, via Gosper's hack.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::GosperCombinations;
+/// let masks: Vec<u64> = GosperCombinations::new(4, 2).collect();
+/// assert_eq!(masks, vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GosperCombinations<M> {
+    next: Option<M>,
+    n: usize,
+}
+
+impl<M: BitMask> GosperCombinations<M> {
+    /// Iterate over the `k`-subsets of `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > M::BITS`.
+    pub fn new(n: usize, k: usize) -> Self {
+        assert!(n <= M::BITS as usize, "{} bits do not fit in a {}-bit mask", n, M::BITS);
+        let next = (k <= n).then(|| low_bits(k));
+        GosperCombinations { next, n }
+    }
+}
+
+impl<M: BitMask> Iterator for GosperCombinations<M> {
+    type Item = M;
+
+    fn next(&mut self) -> Option<M> {
+        let x = self.next?;
+        self.next = next_same_popcount(x, self.n);
+        Some(x)
+    }
+}
+
+/// Iterator over all subsets of a mask in increasing numeric order, from the
+/// empty set up to the mask itself.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::SubsetsOf;
+/// let subs: Vec<u64> = SubsetsOf::new(0b1010).collect();
+/// assert_eq!(subs, vec![0b0000, 0b0010, 0b1000, 0b1010]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubsetsOf<M> {
+    mask: M,
+    next: Option<M>,
+}
+
+impl<M: BitMask> SubsetsOf<M> {
+    /// Iterate over the subsets of `mask`.
+    pub fn new(mask: M) -> Self {
+        SubsetsOf { mask, next: Some(M::ZERO) }
+    }
+}
+
+impl<M: BitMask> Iterator for SubsetsOf<M> {
+    type Item = M;
+
+    fn next(&mut self) -> Option<M> {
+        let s = self.next?;
+        self.next = (s != self.mask)
+            .then(|| (s | !self.mask).checked_add(M::ONE).map(|t| t & self.mask))
+            .flatten();
+        Some(s)
+    }
+}
+
+/// Iterator over all submasks of a mask in decreasing numeric order, from the
+/// mask itself down to the empty set.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::Submasks;
+/// let subs: Vec<u64> = Submasks::new(0b1010).collect();
+/// assert_eq!(subs, vec![0b1010, 0b1000, 0b0010, 0b0000]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Submasks<M> {
+    mask: M,
+    next: Option<M>,
+}
+
+impl<M: BitMask> Submasks<M> {
+    /// Iterate over the submasks of `mask`.
+    pub fn new(mask: M) -> Self {
+        Submasks { mask, next: Some(mask) }
+    }
+}
+
+impl<M: BitMask> Iterator for Submasks<M> {
+    type Item = M;
+
+    fn next(&mut self) -> Option<M> {
+        let s = self.next?;
+        self.next = (s != M::ZERO).then(|| s.wrapping_sub(M::ONE) & self.mask);
+        Some(s)
+    }
+}
+
+/// Convert a sorted index vector to a multi-word bitset over `0..n`.
+///
+/// # Panics
+///
+/// Panics if an index is `≥ n`.
+pub fn indices_to_words(indices: &[usize], n: usize) -> Vec<u64> {
+    let mut words = vec![0u64; n.div_ceil(64)];
+    for &i in indices {
+        assert!(i < n, "index {} out of range for {} elements", i, n);
+        words[i / 64] |= 1 << (i % 64);
+    }
+    words
+}
+
+/// The members of a multi-word bitset in increasing order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::{indices_to_words, words_to_indices};
+/// let words = indices_to_words(&[3, 64, 150], 200);
+/// assert_eq!(words.len(), 4);
+/// assert_eq!(words_to_indices(&words), vec![3, 64, 150]);
+/// ```
+pub fn words_to_indices(words: &[u64]) -> Vec<usize> {
+    words
+        .iter()
+        .enumerate()
+        .flat_map(|(w, &bits)| mask_to_indices(bits).into_iter().map(move |i| w * 64 + i))
+        .collect()
+}
+
+/// Advance a multi-word k-subset of `0..n` to its colex successor, the
+/// multi-word analogue of [`next_same_popcount`].
+///
+/// Moves the lowest run of ones `t..p` up by one: bit `p` is set and the rest
+/// of the run drops to the bottom.
+///
+/// # Returns
+///
+/// `true` if advanced; `false` if `words` held the last k-subset (or the empty
+/// set), in which case it is left unchanged.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::{indices_to_words, next_combination_words, words_to_indices};
+/// let mut w = indices_to_words(&[62, 63, 64], 130);
+/// assert!(next_combination_words(&mut w, 130));
+/// assert_eq!(words_to_indices(&w), vec![0, 1, 65]);
+/// ```
+pub fn next_combination_words(words: &mut [u64], n: usize) -> bool {
+    let bit = |w: &[u64], i: usize| w[i / 64] >> (i % 64) & 1 == 1;
+    let Some(t) = words.iter().position(|&w| w != 0) else {
+        return false;
+    };
+    let t = t * 64 + words[t].trailing_zeros() as usize;
+    let mut p = t;
+    while p < n && bit(words, p) {
+        p += 1;
+    }
+    if p >= n {
+        return false;
+    }
+    for i in t..p {
+        words[i / 64] &= !(1 << (i % 64));
+    }
+    words[p / 64] |= 1 << (p % 64);
+    for i in 0..p - t - 1 {
+        words[i / 64] |= 1 << (i % 64);
+    }
+    true
+}
+
+/// Advance `s` to the next larger subset of the multi-word `mask`, the
+/// multi-word analogue of [`SubsetsOf`].
+///
+/// # Returns
+///
+/// `true` if advanced; `false` if `s` was `mask` itself, in which case it
+/// wraps around to the empty set.
+///
+/// # Panics
+///
+/// Panics if `s` and `mask` have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::{indices_to_words, next_subset_words, words_to_indices};
+/// let mask = indices_to_words(&[5, 63, 64, 130], 131);
+/// let mut s = indices_to_words(&[5, 63], 131);
+/// assert!(next_subset_words(&mut s, &mask));
+/// assert_eq!(words_to_indices(&s), vec![64]);
+/// ```
+pub fn next_subset_words(s: &mut [u64], mask: &[u64]) -> bool {
+    assert_eq!(s.len(), mask.len(), "subset and mask lengths differ");
+    for (w, &m) in s.iter_mut().zip(mask) {
+        let (sum, carry) = (*w | !m).overflowing_add(1);
+        *w = sum & m;
+        if !carry {
+            return true;
+        }
+    }
+    false
+}
+
+/// Advance `s` to the next smaller submask of the multi-word `mask`, the
+/// multi-word analogue of [`Submasks`].
+///
+/// # Returns
+///
+/// `true` if advanced; `false` if `s` was the empty set, in which case it
+/// wraps around to `mask`.
+///
+/// # Panics
+///
+/// Panics if `s` and `mask` have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::{indices_to_words, next_submask_words, words_to_indices};
+/// let mask = indices_to_words(&[5, 63, 64, 130], 131);
+/// let mut s = indices_to_words(&[64], 131);
+/// assert!(next_submask_words(&mut s, &mask));
+/// assert_eq!(words_to_indices(&s), vec![5, 63]);
+/// ```
+pub fn next_submask_words(s: &mut [u64], mask: &[u64]) -> bool {
+    assert_eq!(s.len(), mask.len(), "submask and mask lengths differ");
+    for (w, &m) in s.iter_mut().zip(mask) {
+        let (diff, borrow) = w.overflowing_sub(1);
+        *w = diff & m;
+        if !borrow {
+            return true;
+        }
+    }
+    false
+}
+
+/// Iterator over all subsets of a multi-word mask in increasing numeric order,
+/// from the empty set up to the mask itself.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::{indices_to_words, words_to_indices, SubsetsOfWords};
+/// let mask = indices_to_words(&[1, 200], 201);
+/// let subs: Vec<Vec<usize>> = SubsetsOfWords::new(&mask).map(|s| words_to_indices(&s)).collect();
+/// assert_eq!(subs, vec![vec![], vec![1], vec![200], vec![1, 200]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubsetsOfWords {
+    mask: Vec<u64>,
+    next: Option<Vec<u64>>,
+}
+
+impl SubsetsOfWords {
+    /// Iterate over the subsets of `mask`.
+    pub fn new(mask: &[u64]) -> Self {
+        SubsetsOfWords { mask: mask.to_vec(), next: Some(vec![0; mask.len()]) }
+    }
+}
+
+impl Iterator for SubsetsOfWords {
+    type Item = Vec<u64>;
+
+    fn next(&mut self) -> Option<Vec<u64>> {
+        let s = self.next.take()?;
+        let mut t = s.clone();
+        if next_subset_words(&mut t, &self.mask) {
+            self.next = Some(t);
+        }
+        Some(s)
+    }
+}
+
+/// Iterator over all submasks of a multi-word mask in decreasing numeric
+/// order, from the mask itself down to the empty set.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::bitset::{indices_to_words, words_to_indices, SubmasksWords};
+/// let mask = indices_to_words(&[1, 200], 201);
+/// let subs: Vec<Vec<usize>> = SubmasksWords::new(&mask).map(|s| words_to_indices(&s)).collect();
+/// assert_eq!(subs, vec![vec![1, 200], vec![200], vec![1], vec![]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubmasksWords {
+    mask: Vec<u64>,
+    next: Option<Vec<u64>>,
+}
+
+impl SubmasksWords {
+    /// Iterate over the submasks of `mask`.
+    pub fn new(mask: &[u64]) -> Self {
+        SubmasksWords { mask: mask.to_vec(), next: Some(mask.to_vec()) }
+    }
+}
+
+impl Iterator for SubmasksWords {
+    type Item = Vec<u64>;
+
+    fn next(&mut self) -> Option<Vec<u64>> {
+        let s = self.next.take()?;
+        let mut t = s.clone();
+        if next_submask_words(&mut t, &self.mask) {
+            self.next = Some(t);
+        }
+        Some(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinations::iter::Combinations;
+
+    #[test]
+    fn test_gosper_matches_colex_and_words() {
+        let items: Vec<usize> = (0..10).collect();
+        for k in 0..=10 {
+            let colex: Vec<u64> = Combinations::colex(&items, k)
+                .map(|c| indices_to_mask(&c))
+                .collect();
+            let gosper: Vec<u64> = GosperCombinations::new(10, k).collect();
+            assert_eq!(gosper, colex);
+            assert!(gosper.iter().all(|&m| indices_to_mask::<u64>(&mask_to_indices(m)) == m));
+
+            if k > 0 {
+                let mut words = indices_to_words(&(0..k).collect::<Vec<_>>(), 10);
+                let mut seq = vec![words[0]];
+                while next_combination_words(&mut words, 10) {
+                    seq.push(words[0]);
+                }
+                assert_eq!(seq, colex);
+            }
+        }
+        let mut words = indices_to_words(&[0, 1], 130);
+        let mut count = 1;
+        while next_combination_words(&mut words, 130) {
+            count += 1;
+        }
+        assert_eq!(count, 130 * 129 / 2);
+        assert_eq!(words_to_indices(&words), vec![128, 129]);
+
+        // full-width masks terminate without overflowing
+        assert_eq!(GosperCombinations::<u128>::new(128, 127).count(), 128);
+        assert_eq!(GosperCombinations::<u64>::new(64, 64).collect::<Vec<_>>(), vec![u64::MAX]);
+    }
+
+    #[test]
+    fn test_subset_and_submask_orders() {
+        for mask in [0u128, 0b1, 0b1011_0110, u128::MAX << 124 | 0b101] {
+            let up: Vec<u128> = SubsetsOf::new(mask).collect();
+            let mut down: Vec<u128> = Submasks::new(mask).collect();
+            assert_eq!(up.len(), 1 << mask.count_ones());
+            assert!(up.windows(2).all(|w| w[0] < w[1]));
+            assert!(up.iter().all(|&s| s & !mask == 0));
+            down.reverse();
+            assert_eq!(up, down);
+        }
+    }
+
+    #[test]
+    fn test_multi_word_subsets_and_submasks() {
+        // agrees with the u128 iterators on two words
+        let mask = u128::MAX << 124 | 0b1011_0110;
+        let words = [mask as u64, (mask >> 64) as u64];
+        let as_u128 = |w: Vec<u64>| w[0] as u128 | (w[1] as u128) << 64;
+        let up: Vec<u128> = SubsetsOfWords::new(&words).map(as_u128).collect();
+        let down: Vec<u128> = SubmasksWords::new(&words).map(as_u128).collect();
+        assert_eq!(up, SubsetsOf::new(mask).collect::<Vec<_>>());
+        assert_eq!(down, Submasks::new(mask).collect::<Vec<_>>());
+
+        // four words, with carries and borrows crossing word boundaries
+        let members = [0, 63, 64, 127, 128, 191, 192, 250];
+        let mask = indices_to_words(&members, 251);
+        let up: Vec<Vec<usize>> = SubsetsOfWords::new(&mask).map(|s| words_to_indices(&s)).collect();
+        let mut down: Vec<Vec<usize>> =
+            SubmasksWords::new(&mask).map(|s| words_to_indices(&s)).collect();
+        assert_eq!(up.len(), 1 << members.len());
+        assert!(up.iter().all(|s| s.iter().all(|i| members.contains(i))));
+        // increasing numeric order compares the highest members first
+        assert!(up.windows(2).all(|w| w[0].iter().rev().lt(w[1].iter().rev())));
+        down.reverse();
+        assert_eq!(up, down);
+
+        // both steps wrap around at the end
+        let mut s = mask.clone();
+        assert!(!next_subset_words(&mut s, &mask));
+        assert_eq!(s, vec![0; 4]);
+        assert!(!next_submask_words(&mut s, &mask));
+        assert_eq!(s, mask);
+    }
+}
@@ -0,0 +1,361 @@
+//! # Chapter 5: Combinations — Lazy Iteration in Three Orders
+//!
+//! Iterate over the k-element subsets of a slice without materialising all
+//! `C(n, k)` of them, in lexicographic, colexicographic or revolving-door order.
+//!
+//! ## Algorithm Description
+//!
+//! A combination is a strictly increasing index vector `c[0] < … < c[k-1]`.
+//!
+//! - **Lex**: [`next_combination`] — bump the rightmost index that can move
+//!   and reset the ones after it.
+//! - **Colex**: bump the leftmost index `c[j]` with `c[j] + 1 < c[j+1]` and
+//!   reset `c[i] = i` for `i < j`.
+//! - **Revolving door** (Knuth, TAOCP 7.2.1.3, Algorithm R): consecutive
+//!   combinations differ by one element leaving and one entering. This is the
+//!   same sequence as [`k_subset_gray`](crate::gray::k_subset::k_subset_gray),
+//!   produced one step at a time.
+//!
+//! ## Random Access
+//!
+//! Every order has an unranking function (`unrank_comb`, `unrank_colex`, and
+//! Kreher–Stinson's revolving-door unrank), so [`Combinations::range`] and
+//! [`Iterator::nth`] jump straight to a rank and continue from there. This
+//! lets callers split one enumeration into independent shards.
+//!
+//! ## Complexity
+//!
+//! - Time: O(k) per combination (O(1) amortized for colex / revolving door)
+//! - Seek: O(n · k) for lex and revolving door (O(n) binomials of O(k) each),
+//!   O(k² log n) for colex (O(k log n) binomials)
+//! - Space: O(k)
+
+use std::ops::Range;
+
+use super::lex::next_combination;
+use super::number_system::unrank_colex;
+use super::ranking::{binom, unrank_comb};
+
+/// The order in which [`Combinations`] visits subsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinationOrder {
+    /// Lexicographic order of the index vectors.
+    Lex,
+    /// Colexicographic order: compare the largest indices first.
+    Colex,
+    /// Minimal-change order: one element out, one element in per step.
+    RevolvingDoor,
+}
+
+/// Lazy iterator over the k-combinations of a slice.
+///
+/// Yields owned `Vec<T>` through [`Iterator`]; [`Combinations::next_indices`]
+/// and [`Combinations::next_slice`] lend the current combination without
+/// allocating.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::iter::Combinations;
+/// let it = Combinations::lex(&['a', 'b', 'c', 'd'], 2);
+/// assert_eq!(it.len(), 6);
+/// let all: Vec<String> = it.map(|c| c.into_iter().collect()).collect();
+/// assert_eq!(all, ["ab", "ac", "ad", "bc", "bd", "cd"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    order: CombinationOrder,
+    indices: Vec<usize>,
+    buf: Vec<T>,
+    started: bool,
+    next: usize,
+    end: usize,
+}
+
+impl<'a, T: Clone> Combinations<'a, T> {
+    /// Iterate over the `k`-subsets of `items` in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C(n, k)` does not fit in `usize`.
+    pub fn new(items: &'a [T], k: usize, order: CombinationOrder) -> Self {
+        let total = binom(items.len(), k);
+        Combinations::range(items, k, order, 0..total)
+    }
+
+    /// Iterate over the combinations whose ranks in `order` lie in `ranks`.
+    ///
+    /// The first combination is unranked directly, so the cost of starting at
+    /// rank `r` does not depend on `r`. Disjoint ranges can be handed to
+    /// different workers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ranks.start > ranks.end` or `ranks.end > C(n, k)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::combinations::iter::{CombinationOrder, Combinations};
+    /// let items: Vec<usize> = (0..6).collect();
+    /// let shard: Vec<Vec<usize>> =
+    ///     Combinations::range(&items, 3, CombinationOrder::Lex, 10..13).collect();
+    /// assert_eq!(shard, vec![vec![1, 2, 3], vec![1, 2, 4], vec![1, 2, 5]]);
+    /// ```
+    pub fn range(items: &'a [T], k: usize, order: CombinationOrder, ranks: Range<usize>) -> Self {
+        let total = binom(items.len(), k);
+        assert!(
+            ranks.start <= ranks.end && ranks.end <= total,
+            "rank range {:?} out of bounds for {} combinations",
+            ranks,
+            total
+        );
+        let mut it = Combinations {
+            items,
+            order,
+            indices: (0..k).collect(),
+            buf: Vec::with_capacity(k),
+            started: false,
+            next: ranks.start,
+            end: ranks.end,
+        };
+        it.seek(ranks.start);
+        it
+    }
+
+    /// Lexicographic order.
+    pub fn lex(items: &'a [T], k: usize) -> Self {
+        Combinations::new(items, k, CombinationOrder::Lex)
+    }
+
+    /// Colexicographic order.
+    pub fn colex(items: &'a [T], k: usize) -> Self {
+        Combinations::new(items, k, CombinationOrder::Colex)
+    }
+
+    /// Revolving-door (minimal-change) order.
+    pub fn revolving_door(items: &'a [T], k: usize) -> Self {
+        Combinations::new(items, k, CombinationOrder::RevolvingDoor)
+    }
+
+    /// Advance and lend the indices of the next combination.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::combinations::iter::Combinations;
+    /// let mut it = Combinations::colex(&[10, 20, 30], 2);
+    /// assert_eq!(it.next_indices(), Some(&[0, 1][..]));
+    /// assert_eq!(it.next_indices(), Some(&[0, 2][..]));
+    /// assert_eq!(it.next_indices(), Some(&[1, 2][..]));
+    /// assert_eq!(it.next_indices(), None);
+    /// ```
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        if self.next == self.end {
+            return None;
+        }
+        if self.started {
+            let n = self.items.len();
+            let c = &mut self.indices;
+            let advanced = match self.order {
+                CombinationOrder::Lex => next_combination(c, n),
+                CombinationOrder::Colex => next_colex(c, n),
+                CombinationOrder::RevolvingDoor => next_revolving_door(c, n),
+            };
+            debug_assert!(advanced, "remaining count out of sync");
+        }
+        self.started = true;
+        self.next += 1;
+        Some(&self.indices)
+    }
+
+    /// Advance and lend the items of the next combination.
+    ///
+    /// The items are cloned into an internal buffer that is reused across
+    /// calls, so no allocation happens after the first step.
+    pub fn next_slice(&mut self) -> Option<&[T]> {
+        self.next_indices()?;
+        self.buf.clear();
+        self.buf.extend(self.indices.iter().map(|&i| self.items[i].clone()));
+        Some(&self.buf)
+    }
+
+    /// Position the iterator so that the next combination yielded has `rank`.
+    fn seek(&mut self, rank: usize) {
+        self.next = rank;
+        self.started = false;
+        if rank == self.end {
+            return;
+        }
+        let (n, k) = (self.items.len(), self.indices.len());
+        self.indices = match self.order {
+            CombinationOrder::Lex => unrank_comb(rank, n, k),
+            CombinationOrder::Colex => unrank_colex(rank, k),
+            CombinationOrder::RevolvingDoor => unrank_revolving_door(rank, n, k),
+        };
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        self.next_slice().map(<[T]>::to_vec)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.next;
+        (remaining, Some(remaining))
+    }
+
+    /// Jumps to the target rank by unranking instead of stepping.
+    fn nth(&mut self, m: usize) -> Option<Vec<T>> {
+        if m >= self.end - self.next {
+            self.next = self.end;
+            return None;
+        }
+        if m > 0 {
+            self.seek(self.next + m);
+        }
+        self.next()
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for Combinations<'_, T> {}
+
+/// Colex successor: bump the leftmost index that has room.
+fn next_colex(c: &mut [usize], n: usize) -> bool {
+    let k = c.len();
+    for j in 0..k {
+        let limit = if j + 1 < k { c[j + 1] } else { n };
+        if c[j] + 1 < limit {
+            c[j] += 1;
+            for (i, x) in c[..j].iter_mut().enumerate() {
+                *x = i;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Revolving-door successor (Knuth's Algorithm R, steps R3–R5), 0-based.
+fn next_revolving_door(c: &mut [usize], n: usize) -> bool {
+    let t = c.len();
+    if t == 0 {
+        return false;
+    }
+    let at = |c: &[usize], j: usize| if j < t { c[j] } else { n };
+    // R3: easy cases on the smallest element
+    let mut try_decrease = if t % 2 == 1 {
+        if c[0] + 1 < at(c, 1) {
+            c[0] += 1;
+            return true;
+        }
+        true
+    } else {
+        if c[0] > 0 {
+            c[0] -= 1;
+            return true;
+        }
+        false
+    };
+    let mut j = 1;
+    while j < t {
+        if try_decrease {
+            // R4: here c[j] = c[j-1] + 1
+            if c[j] > j {
+                c[j] = c[j - 1];
+                c[j - 1] = j - 1;
+                return true;
+            }
+        } else {
+            // R5: here c[j-1] = j - 1
+            if c[j] + 1 < at(c, j + 1) {
+                c[j - 1] = c[j];
+                c[j] += 1;
+                return true;
+            }
+        }
+        j += 1;
+        try_decrease = !try_decrease;
+    }
+    false
+}
+
+/// Revolving-door unrank (Kreher & Stinson, Algorithm 2.12), 0-based.
+fn unrank_revolving_door(mut rank: usize, n: usize, k: usize) -> Vec<usize> {
+    let mut c = vec![0; k];
+    let mut x = n;
+    for i in (1..=k).rev() {
+        while binom(x, i) > rank {
+            x -= 1;
+        }
+        c[i - 1] = x;
+        rank = binom(x + 1, i) - rank - 1;
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinations::lex::all_combinations;
+    use crate::gray::k_subset::k_subset_gray;
+
+    #[test]
+    fn test_orders_match_reference_sequences() {
+        let items: Vec<usize> = (0..7).collect();
+        for k in 0..=7 {
+            let lex: Vec<Vec<usize>> = Combinations::lex(&items, k).collect();
+            assert_eq!(lex, all_combinations(7, k));
+
+            let mut colex: Vec<Vec<usize>> = Combinations::colex(&items, k).collect();
+            assert!(colex.windows(2).all(|w| {
+                w[0].iter().rev().cmp(w[1].iter().rev()) == std::cmp::Ordering::Less
+            }));
+            colex.sort();
+            assert_eq!(colex, lex);
+
+            let rd: Vec<Vec<usize>> = Combinations::revolving_door(&items, k).collect();
+            assert_eq!(rd, k_subset_gray(7, k));
+        }
+        assert_eq!(Combinations::lex(&items, 8).count(), 0);
+    }
+
+    #[test]
+    fn test_exact_size_and_lending() {
+        let words = ["x", "y", "z", "w", "v"];
+        let mut it = Combinations::revolving_door(&words, 3);
+        assert_eq!(it.len(), 10);
+        let mut seen = 0;
+        while let Some(c) = it.next_slice() {
+            assert_eq!(c.len(), 3);
+            seen += 1;
+            assert_eq!(it.len(), 10 - seen);
+        }
+        assert_eq!(seen, 10);
+    }
+
+    #[test]
+    fn test_range_and_nth_match_stepping() {
+        let items: Vec<usize> = (0..8).collect();
+        for order in [CombinationOrder::Lex, CombinationOrder::Colex, CombinationOrder::RevolvingDoor] {
+            for k in 0..=8 {
+                let all: Vec<Vec<usize>> = Combinations::new(&items, k, order).collect();
+                let total = all.len();
+                for start in 0..=total {
+                    let shard: Vec<Vec<usize>> =
+                        Combinations::range(&items, k, order, start..total).collect();
+                    assert_eq!(shard, all[start..]);
+                }
+                let mut it = Combinations::new(&items, k, order);
+                assert_eq!(it.nth(total / 2), all.get(total / 2).cloned());
+                assert_eq!(it.next(), all.get(total / 2 + 1).cloned());
+                let skipped: Vec<Vec<usize>> = Combinations::new(&items, k, order).skip(3).collect();
+                assert_eq!(skipped, all[3.min(total)..]);
+            }
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod binomial;
+pub mod bitset;
+pub mod iter;
 pub mod lex;
+pub mod multiset;
+pub mod number_system;
 pub mod random;
 pub mod ranking;
+pub mod sampling;
@@ -0,0 +1,328 @@
+//! # Chapter 5: Combinations — Combinations with Repetition
+//!
+//! A **k-multicombination** of `n` types is a non-decreasing sequence
+//! `a₀ ≤ a₁ ≤ … ≤ a_{k-1}` of values in `0..n` (choose k items, repetition
+//! allowed). A **k-sub-multiset** additionally bounds how often each type may
+//! repeat: type `i` appears at most `mult[i]` times.
+//!
+//! ## Algorithm Description
+//!
+//! **Unbounded (stars and bars)**: `a ↦ (a₀ + 0, a₁ + 1, …, a_{k-1} + k-1)` is an
+//!   order-preserving bijection onto the k-subsets of `0..n+k-1`, so counting,
+//!   ranking and unranking reuse [`binom`], [`rank_comb`] and [`unrank_comb`].
+//! **Bounded**: with `F(i, r)` = number of r-sub-multisets using only types
+//!   `i..t`, lex order lists sub-multisets with *more* copies of type `i` first.
+//!   Ranking adds `F(i+1, r - x)` for every count `x` larger than the actual one;
+//!   unranking walks the same blocks (see [`find_block`]). With 130 types of
+//!   multiplicity 1 the entries are binomials, so `F(0, 65)` overflows while
+//!   `F(0, 130) = 1`; an overflowing entry is `None` and never spoils the
+//!   exact entries built beside it.
+//! **Successor**: bump the rightmost entry that can grow, then refill the tail
+//!   with the smallest values allowed.
+//!
+//! ## Complexity
+//!
+//! - Unbounded rank / unrank: as for k-subsets of `n + k - 1`
+//! - Bounded count / rank / unrank: O(t · k²), dominated by building the
+//!   table on each call; the walk itself is O(t · k)
+//! - Successor: O(k · t) in the worst case, since each position tried
+//!   may scan every type above it
+
+use crate::arith::rank_int::find_block;
+
+use super::ranking::{binom, rank_comb, unrank_comb};
+
+/// Number of k-multicombinations of `n` types, `C(n + k - 1, k)`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::multichoose;
+/// assert_eq!(multichoose(3, 2), 6); // 00 01 02 11 12 22
+/// ```
+pub fn multichoose(n: usize, k: usize) -> usize {
+    if n == 0 {
+        return (k == 0) as usize;
+    }
+    binom(n + k - 1, k)
+}
+
+/// Advance a multicombination of `n` types to its lex successor.
+///
+/// # Returns
+///
+/// `true` if advanced; `false` if `a` was already the last one.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::next_multicombination;
+/// let mut a = vec![0, 2, 2];
+/// assert!(next_multicombination(&mut a, 3));
+/// assert_eq!(a, vec![1, 1, 1]);
+/// ```
+pub fn next_multicombination(a: &mut [usize], n: usize) -> bool {
+    let Some(i) = a.iter().rposition(|&x| x + 1 < n) else {
+        return false;
+    };
+    let v = a[i] + 1;
+    a[i..].iter_mut().for_each(|x| *x = v);
+    true
+}
+
+/// All k-multicombinations of `n` types in lex order.
+pub fn all_multicombinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if n == 0 && k > 0 {
+        return Vec::new();
+    }
+    let mut a = vec![0; k];
+    let mut out = vec![a.clone()];
+    while next_multicombination(&mut a, n) {
+        out.push(a.clone());
+    }
+    out
+}
+
+/// Lex rank of a multicombination of `n` types.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::{rank_multicombination, unrank_multicombination};
+/// assert_eq!(rank_multicombination(&[1, 1], 3), 3);
+/// assert_eq!(unrank_multicombination(3, 3, 2), vec![1, 1]);
+/// ```
+pub fn rank_multicombination(a: &[usize], n: usize) -> usize {
+    if a.is_empty() {
+        return 0;
+    }
+    let shifted: Vec<usize> = a.iter().enumerate().map(|(i, &x)| x + i).collect();
+    rank_comb(&shifted, n + a.len() - 1)
+}
+
+/// The k-multicombination of `n` types with the given lex rank.
+pub fn unrank_multicombination(rank: usize, n: usize, k: usize) -> Vec<usize> {
+    if k == 0 {
+        return Vec::new();
+    }
+    unrank_comb(rank, n + k - 1, k)
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| x - i)
+        .collect()
+}
+
+/// Number of k-sub-multisets of a multiset with the given multiplicities.
+///
+/// # Panics
+///
+/// Panics if the count does not fit in `usize`; see
+/// [`checked_count_submultisets`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::count_submultisets;
+/// // {a, a, b}: choose 2 → aa, ab
+/// assert_eq!(count_submultisets(&[2, 1], 2), 2);
+/// ```
+pub fn count_submultisets(mult: &[usize], k: usize) -> usize {
+    checked_count_submultisets(mult, k).expect("sub-multiset count overflows usize")
+}
+
+/// Number of k-sub-multisets of a multiset with the given multiplicities, or
+/// `None` if it does not fit in `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::checked_count_submultisets;
+/// assert_eq!(checked_count_submultisets(&[1; 130], 130), Some(1));
+/// assert_eq!(checked_count_submultisets(&[1; 130], 65), None);
+/// ```
+pub fn checked_count_submultisets(mult: &[usize], k: usize) -> Option<usize> {
+    submultiset_table(mult, k)[0][k]
+}
+
+/// Advance a k-sub-multiset (non-decreasing type indices) to its lex successor.
+///
+/// # Returns
+///
+/// `true` if advanced; `false` if `a` was already the last one.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::next_submultiset;
+/// let mut a = vec![0, 0, 1];
+/// assert!(next_submultiset(&mut a, &[2, 1, 2]));
+/// assert_eq!(a, vec![0, 0, 2]);
+/// ```
+pub fn next_submultiset(a: &mut [usize], mult: &[usize]) -> bool {
+    let k = a.len();
+    // tail[v] = total capacity of types v..t
+    let mut tail = vec![0; mult.len() + 1];
+    for v in (0..mult.len()).rev() {
+        tail[v] = tail[v + 1] + mult[v];
+    }
+    for i in (0..k).rev() {
+        // types above a[i] are unused by the prefix a[..i]
+        let Some(v) = (a[i] + 1..mult.len()).find(|&v| mult[v] > 0 && tail[v] >= k - i) else {
+            continue;
+        };
+        let (mut ty, mut used) = (v, 0);
+        for slot in &mut a[i..] {
+            while used == mult[ty] {
+                ty += 1;
+                used = 0;
+            }
+            *slot = ty;
+            used += 1;
+        }
+        return true;
+    }
+    false
+}
+
+/// All k-sub-multisets of a multiset with the given multiplicities, in lex order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::all_submultisets;
+/// let subs = all_submultisets(&[2, 1], 2);
+/// assert_eq!(subs, vec![vec![0, 0], vec![0, 1]]);
+/// ```
+pub fn all_submultisets(mult: &[usize], k: usize) -> Vec<Vec<usize>> {
+    if checked_count_submultisets(mult, k) == Some(0) {
+        return Vec::new();
+    }
+    let mut a = unrank_submultiset(0, mult, k);
+    let mut out = vec![a.clone()];
+    while next_submultiset(&mut a, mult) {
+        out.push(a.clone());
+    }
+    out
+}
+
+/// Lex rank of a sub-multiset given as non-decreasing type indices.
+///
+/// # Panics
+///
+/// Panics if the rank does not fit in `usize`.
+pub fn rank_submultiset(a: &[usize], mult: &[usize]) -> usize {
+    let k = a.len();
+    let f = submultiset_table(mult, k);
+    let mut rank = 0usize;
+    let mut rem = k;
+    for (i, &m) in mult.iter().enumerate() {
+        let x = a.iter().filter(|&&v| v == i).count();
+        for more in x + 1..=m.min(rem) {
+            rank = f[i + 1][rem - more]
+                .and_then(|block| rank.checked_add(block))
+                .expect("sub-multiset rank overflows usize");
+        }
+        rem -= x;
+    }
+    rank
+}
+
+/// The k-sub-multiset with the given lex rank.
+///
+/// # Panics
+///
+/// Panics if the rank is out of range.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::multiset::{rank_submultiset, unrank_submultiset};
+/// let mult = [2, 1, 2];
+/// let a = unrank_submultiset(2, &mult, 3);
+/// assert_eq!(a, vec![0, 1, 2]);
+/// assert_eq!(rank_submultiset(&a, &mult), 2);
+/// ```
+pub fn unrank_submultiset(mut rank: usize, mult: &[usize], k: usize) -> Vec<usize> {
+    let f = submultiset_table(mult, k);
+    let mut out = Vec::with_capacity(k);
+    let mut rem = k;
+    for (i, &m) in mult.iter().enumerate() {
+        let top = m.min(rem);
+        let blocks = (0..=top).rev().map(|x| f[i + 1][rem - x].as_ref());
+        let x = top - find_block(&mut rank, blocks).expect("sub-multiset rank out of range");
+        out.extend(std::iter::repeat_n(i, x));
+        rem -= x;
+    }
+    out
+}
+
+/// `f[i][r]` = number of r-sub-multisets drawn from types `i..t`, or `None`
+/// if that number does not fit in `usize`.
+fn submultiset_table(mult: &[usize], k: usize) -> Vec<Vec<Option<usize>>> {
+    let t = mult.len();
+    let mut f = vec![vec![Some(0usize); k + 1]; t + 1];
+    f[t][0] = Some(1);
+    for i in (0..t).rev() {
+        for r in 0..=k {
+            f[i][r] = (0..=mult[i].min(r))
+                .try_fold(0usize, |acc, x| acc.checked_add(f[i + 1][r - x]?));
+        }
+    }
+    f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multicombinations_rank_roundtrip() {
+        for n in 0..=5 {
+            for k in 0..=4 {
+                let all = all_multicombinations(n, k);
+                assert_eq!(all.len(), multichoose(n, k));
+                for (r, a) in all.iter().enumerate() {
+                    assert_eq!(rank_multicombination(a, n), r);
+                    assert_eq!(unrank_multicombination(r, n, k), *a);
+                }
+            }
+        }
+        assert_eq!(rank_multicombination(&[], 0), 0);
+        assert_eq!(unrank_multicombination(0, 0, 0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_submultisets_match_unbounded_and_brute_force() {
+        // unbounded case: every multiplicity ≥ k
+        assert_eq!(all_submultisets(&[4, 4, 4], 4), all_multicombinations(3, 4));
+
+        let mult = [2, 0, 3, 1];
+        for k in 0..=7 {
+            let mut brute: Vec<Vec<usize>> = all_multicombinations(4, k)
+                .into_iter()
+                .filter(|a| (0..4).all(|v| a.iter().filter(|&&x| x == v).count() <= mult[v]))
+                .collect();
+            brute.sort();
+            let subs = all_submultisets(&mult, k);
+            assert_eq!(subs, brute);
+            assert_eq!(count_submultisets(&mult, k), subs.len());
+            for (r, a) in subs.iter().enumerate() {
+                assert_eq!(rank_submultiset(a, &mult), r);
+                assert_eq!(unrank_submultiset(r, &mult, k), *a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_submultisets_survive_overflowing_cells() {
+        // cells such as C(129, 64) overflow, but none of them feeds the answer
+        assert_eq!(count_submultisets(&[1; 130], 130), 1);
+        let all: Vec<usize> = (0..130).collect();
+        assert_eq!(all_submultisets(&[1; 130], 130), vec![all.clone()]);
+        assert_eq!(rank_submultiset(&all, &[1; 130]), 0);
+        assert_eq!(checked_count_submultisets(&[1; 130], 65), None);
+        let first: Vec<usize> = (0..65).collect();
+        assert_eq!(unrank_submultiset(0, &[1; 130], 65), first);
+        assert_eq!(rank_submultiset(&first, &[1; 130]), 0);
+    }
+}
@@ -0,0 +1,236 @@
+//! # Chapter 5: Combinations — Combinatorial Number System
+//!
+//! Every k-combination `c₀ < c₁ < … < c_{k-1}` of non-negative integers has the
+//! unique colexicographic rank
+//!
+//! ```text
+//! rank(c) = C(c₀, 1) + C(c₁, 2) + … + C(c_{k-1}, k)
+//! ```
+//!
+//! (Macaulay's k-binomial representation). The rank does not depend on `n`, so
+//! the same ranks index k-subsets of every ground set.
+//!
+//! ## Algorithm Description
+//!
+//! **Ranking**: sum one table lookup per element.
+//! **Unranking**: for `i = k, …, 1`, binary-search the largest `c` with
+//!   `C(c, i) ≤ rank`, emit it and subtract `C(c, i)`.
+//! **Lex ↔ colex**: the complement map `x ↦ n-1-x` reverses lex order into
+//!   colex order, so `lex_rank(c) = C(n, k) - 1 - colex_rank(dual(c))`.
+//!
+//! ## Complexity
+//!
+//! - Rank: O(k) with a [`BinomialTable`] (O(k²) without)
+//! - Unrank: O(k log n) with a table
+//! - Space: O(k)
+
+use super::binomial::{checked_binom, BinomialTable};
+
+/// Colex rank of a sorted combination, computing binomials on the fly.
+///
+/// # Panics
+///
+/// Panics if the rank does not fit in `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::number_system::rank_colex;
+/// assert_eq!(rank_colex(&[0, 1, 2]), 0);
+/// assert_eq!(rank_colex(&[0, 1, 3]), 1);
+/// assert_eq!(rank_colex(&[1, 3, 4]), 8);
+/// ```
+pub fn rank_colex(comb: &[usize]) -> usize {
+    comb.iter()
+        .enumerate()
+        .try_fold(0usize, |rank, (i, &c)| rank.checked_add(checked_binom(c, i + 1)?))
+        .expect("colex rank overflows usize")
+}
+
+/// Colex rank of a sorted combination in O(k) using a precomputed table.
+///
+/// The table must cover the largest element and `k`.
+///
+/// # Panics
+///
+/// Panics if the table is too small, or if the rank does not fit in `usize`.
+pub fn rank_colex_with(table: &BinomialTable, comb: &[usize]) -> usize {
+    comb.iter()
+        .enumerate()
+        .try_fold(0usize, |rank, (i, &c)| rank.checked_add(table.get(c, i + 1)?))
+        .expect("colex rank overflows usize")
+}
+
+/// The k-combination with the given colex rank, computing binomials on the fly.
+///
+/// # Panics
+///
+/// Panics if `k = 0` and `rank > 0`: the empty combination is the only one.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::number_system::unrank_colex;
+/// assert_eq!(unrank_colex(8, 3), vec![1, 3, 4]);
+/// assert_eq!(unrank_colex(usize::MAX, 1), vec![usize::MAX]);
+/// ```
+pub fn unrank_colex(rank: usize, k: usize) -> Vec<usize> {
+    match k {
+        0 => {
+            assert_eq!(rank, 0, "colex rank out of range for k = 0");
+            return Vec::new();
+        }
+        1 => return vec![rank],
+        _ => {}
+    }
+    // gallop for an upper bound on the largest element; C(rank + k, k) > rank
+    let cap = rank.saturating_add(k);
+    let mut hi = k;
+    while hi < cap && checked_binom(hi, k).is_some_and(|c| c <= rank) {
+        hi = hi.saturating_mul(2).min(cap);
+    }
+    unrank_by(rank, k, hi, checked_binom)
+}
+
+/// The k-combination with the given colex rank, in O(k log n) using a table.
+///
+/// # Panics
+///
+/// Panics if the combination would need elements beyond the table's `n_max`.
+pub fn unrank_colex_with(table: &BinomialTable, rank: usize, k: usize) -> Vec<usize> {
+    let hi = table.n_max() + 1;
+    assert!(
+        k == 0 || table.get(hi - 1, k).is_none_or(|c| c > rank),
+        "rank {} is out of range for the table",
+        rank
+    );
+    unrank_by(rank, k, hi, |c, i| table.get(c, i))
+}
+
+/// Shared greedy unranking: every element lies below `hi`, and `value` is
+/// `None` for binomials that overflow `usize`.
+fn unrank_by(
+    mut rank: usize,
+    k: usize,
+    mut hi: usize,
+    value: impl Fn(usize, usize) -> Option<usize>,
+) -> Vec<usize> {
+    let mut comb = vec![0; k];
+    for i in (1..=k).rev() {
+        // largest c in [i-1, hi) with C(c, i) ≤ rank; C(i-1, i) = 0 always qualifies
+        let (mut lo, mut up) = (i - 1, hi);
+        while up - lo > 1 {
+            let mid = lo + (up - lo) / 2;
+            if value(mid, i).is_some_and(|c| c <= rank) {
+                lo = mid;
+            } else {
+                up = mid;
+            }
+        }
+        comb[i - 1] = lo;
+        rank -= value(lo, i).expect("C(lo, i) ≤ rank fits");
+        hi = lo;
+    }
+    comb
+}
+
+/// The complement image `{n-1-x}` of a combination, sorted.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::number_system::dual;
+/// assert_eq!(dual(&[0, 2, 3], 6), vec![2, 3, 5]);
+/// ```
+pub fn dual(comb: &[usize], n: usize) -> Vec<usize> {
+    comb.iter().rev().map(|&x| n - 1 - x).collect()
+}
+
+/// Convert the lex rank of a k-subset of `0..n` to its colex rank.
+///
+/// The table must cover `n` and `k`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::binomial::BinomialTable;
+/// use rusty_combinatorial::combinations::number_system::{colex_to_lex_rank, lex_to_colex_rank};
+/// let t = BinomialTable::new(6, 3);
+/// // [1, 3, 4] has lex rank 13 and colex rank 8 among the 3-subsets of 0..6
+/// assert_eq!(lex_to_colex_rank(&t, 13, 6, 3), 8);
+/// assert_eq!(colex_to_lex_rank(&t, 8, 6, 3), 13);
+/// ```
+pub fn lex_to_colex_rank(table: &BinomialTable, rank: usize, n: usize, k: usize) -> usize {
+    let total = table.binom(n, k);
+    let comb = dual(&unrank_colex_with(table, total - 1 - rank, k), n);
+    rank_colex_with(table, &comb)
+}
+
+/// Convert the colex rank of a k-subset of `0..n` to its lex rank.
+///
+/// The table must cover `n` and `k`.
+pub fn colex_to_lex_rank(table: &BinomialTable, rank: usize, n: usize, k: usize) -> usize {
+    let total = table.binom(n, k);
+    let comb = unrank_colex_with(table, rank, k);
+    total - 1 - rank_colex_with(table, &dual(&comb, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinations::iter::Combinations;
+    use crate::combinations::ranking::rank_comb;
+
+    #[test]
+    fn test_cns_matches_colex_iteration() {
+        let items: Vec<usize> = (0..9).collect();
+        let table = BinomialTable::new(9, 9);
+        for k in 0..=9 {
+            for (r, c) in Combinations::colex(&items, k).enumerate() {
+                assert_eq!(rank_colex(&c), r);
+                assert_eq!(rank_colex_with(&table, &c), r);
+                assert_eq!(unrank_colex(r, k), c);
+                assert_eq!(unrank_colex_with(&table, r, k), c);
+                let lex = rank_comb(&c, 9);
+                assert_eq!(colex_to_lex_rank(&table, r, 9, k), lex);
+                assert_eq!(lex_to_colex_rank(&table, lex, 9, k), r);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cns_large_ranks() {
+        let table = BinomialTable::new(1000, 6);
+        let c = vec![3, 141, 592, 653, 897, 932];
+        let r = rank_colex_with(&table, &c);
+        assert_eq!(r, rank_colex(&c));
+        assert_eq!(unrank_colex_with(&table, r, 6), c);
+        assert_eq!(unrank_colex(r, 6), c);
+    }
+
+    #[test]
+    fn test_unrank_colex_extreme_ranks() {
+        assert_eq!(unrank_colex(0, 0), Vec::<usize>::new());
+        assert_eq!(unrank_colex(usize::MAX, 1), vec![usize::MAX]);
+        assert_eq!(rank_colex(&unrank_colex(usize::MAX, 1)), usize::MAX);
+        for k in 2..=6 {
+            let c = unrank_colex(usize::MAX, k);
+            assert_eq!(c.len(), k);
+            assert_eq!(rank_colex(&c), usize::MAX);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "colex rank out of range for k = 0")]
+    fn test_unrank_colex_rejects_rank_for_k_zero() {
+        unrank_colex(1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "colex rank overflows usize")]
+    fn test_rank_colex_panics_when_the_sum_overflows() {
+        // every term fits, but the rank is C(4801281, 3) − 1 > usize::MAX
+        let c = 4_801_280;
+        rank_colex(&[c - 2, c - 1, c]);
+    }
+}
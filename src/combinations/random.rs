@@ -5,19 +5,33 @@
 //!
 //! ## Algorithm Description
 //!
-//! Uses the `rand` crate’s index sampling to select k distinct indices
-//! from 0..n, then sorts them.
+//! Robert Floyd's sampling algorithm:
+//!
+//! - For `j` in `n-k..n`, draw `t` uniformly from `0..=j`.
+//! - Insert `t` if it is not chosen yet, otherwise insert `j` itself.
+//!
+//! Each k-subset is produced with probability `1 / C(n, k)`.
 //!
 //! ## Complexity
 //!
-//! - Time: O(k) expected
+//! - Time: O(k log k)
 //! - Space: O(k)
+//!
+//! ## Determinism
+//!
+//! `random_combination_with` draws exactly `k` values `gen_range(0..=j)` for
+//! `j = n-k, …, n-1`, in that order. The draw sequence is part of the API, so
+//! for a seeded RNG the result is stable across releases of this crate.
+
+use std::collections::BTreeSet;
 
-use rand::seq::index::sample;
-use rand::thread_rng;
+use rand::Rng;
 
 /// Returns a random k-combination from 0..n (sorted).
 ///
+/// Uses `rand::thread_rng()`; see [`random_combination_with`] for a seedable
+/// variant.
+///
 /// # Arguments
 ///
 /// * `n` — Size of the base set
@@ -36,15 +50,42 @@ use rand::thread_rng;
 /// assert!(c.windows(2).all(|w| w[0] < w[1]));
 /// ```
 pub fn random_combination(n: usize, k: usize) -> Vec<usize> {
-    let mut rng = thread_rng();
-    let mut c = sample(&mut rng, n, k).into_vec();
-    c.sort_unstable();
-    c
+    random_combination_with(n, k, &mut rand::thread_rng())
+}
+
+/// Returns a random k-combination from 0..n (sorted) using the given RNG.
+///
+/// # Arguments
+///
+/// * `n` — Size of the base set
+/// * `k` — Number of elements to choose (`k ≤ n`)
+/// * `rng` — Source of randomness
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::combinations::random::random_combination_with;
+/// let mut rng = StdRng::seed_from_u64(1);
+/// let c = random_combination_with(20, 5, &mut rng);
+/// assert_eq!(c, random_combination_with(20, 5, &mut StdRng::seed_from_u64(1)));
+/// ```
+pub fn random_combination_with<R: Rng + ?Sized>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    assert!(k <= n, "cannot choose {} of {} elements", k, n);
+    let mut chosen = BTreeSet::new();
+    for j in n - k..n {
+        let t = rng.gen_range(0..=j);
+        if !chosen.insert(t) {
+            chosen.insert(j);
+        }
+    }
+    chosen.into_iter().collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_random_combination_bounds() {
@@ -52,4 +93,10 @@ mod tests {
         assert_eq!(c.len(), 4);
         assert!(c.iter().all(|&x| x < 10));
     }
+
+    #[test]
+    fn test_random_combination_seeded_fixture() {
+        let c = random_combination_with(50, 6, &mut StdRng::seed_from_u64(42));
+        assert_eq!(c, vec![1, 19, 20, 23, 29, 42]);
+    }
 }
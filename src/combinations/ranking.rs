@@ -12,11 +12,24 @@
 //!
 //! ## Complexity
 //!
-//! - Time: O(k²) (or O(k log n) with fast binomial)
+//! - Time: O(n) table lookups, or O(n·k) computing binomials on the fly
 //! - Space: O(1) extra
+//!
+//! [`rank_comb`] and [`unrank_comb`] compute each binomial on the fly with
+//! [`checked_binom`], in O(k) time and O(1) space apiece. The `_with` variants
+//! read them from a [`BinomialTable`] built once by the caller, so every term
+//! is an O(1) lookup.
+
+use super::binomial::{checked_binom, BinomialTable};
 
 /// Compute C(n, k) = n choose k in O(k) time.
 ///
+/// # Panics
+///
+/// Panics if the result does not fit in `usize`; see
+/// [`checked_binom`](super::binomial::checked_binom) and its siblings for
+/// non-panicking and wider variants.
+///
 /// # Example
 ///
 /// ```
@@ -24,12 +37,7 @@
 /// assert_eq!(binom(5, 3), 10);
 /// ```
 pub fn binom(n: usize, k: usize) -> usize {
-    if k > n { return 0; }
-    let mut res = 1;
-    for i in 0..k {
-        res = res * (n - i) / (i + 1);
-    }
-    res
+    checked_binom(n, k).expect("binomial coefficient overflows usize")
 }
 
 /// Rank the k-combination `comb` among all combinations of `n`.
@@ -51,16 +59,7 @@ pub fn binom(n: usize, k: usize) -> usize {
 /// assert_eq!(rank, 13);
 /// ```
 pub fn rank_comb(comb: &[usize], n: usize) -> usize {
-    let k = comb.len();
-    let mut rank = 0;
-    let mut prev = 0;
-    for (i, &c) in comb.iter().enumerate() {
-        for j in prev..c {
-            rank += binom(n - j - 1, k - i - 1);
-        }
-        prev = c + 1;
-    }
-    rank
+    rank_by(comb, n, binom)
 }
 
 /// Unrank to retrieve the k-combination at position `rank` among C(n,k).
@@ -82,12 +81,54 @@ pub fn rank_comb(comb: &[usize], n: usize) -> usize {
 /// let comb = unrank_comb(13, 6, 3);
 /// assert_eq!(comb, vec![1,3,4]);
 /// ```
-pub fn unrank_comb(mut rank: usize, n: usize, k: usize) -> Vec<usize> {
+pub fn unrank_comb(rank: usize, n: usize, k: usize) -> Vec<usize> {
+    unrank_by(rank, n, k, binom)
+}
+
+/// [`rank_comb`] reading binomials from a precomputed table.
+///
+/// The table must cover `n - 1` and `k - 1`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::binomial::BinomialTable;
+/// use rusty_combinatorial::combinations::ranking::rank_comb_with;
+/// let table = BinomialTable::new(6, 3);
+/// assert_eq!(rank_comb_with(&table, &[1, 3, 4], 6), 13);
+/// ```
+pub fn rank_comb_with(table: &BinomialTable, comb: &[usize], n: usize) -> usize {
+    rank_by(comb, n, |n, k| table.binom(n, k))
+}
+
+/// [`unrank_comb`] reading binomials from a precomputed table.
+///
+/// The table must cover `n - 1` and `k - 1`.
+pub fn unrank_comb_with(table: &BinomialTable, rank: usize, n: usize, k: usize) -> Vec<usize> {
+    unrank_by(rank, n, k, |n, k| table.binom(n, k))
+}
+
+/// Shared lex ranking, with `value(n, k)` supplying `C(n, k)`.
+fn rank_by(comb: &[usize], n: usize, value: impl Fn(usize, usize) -> usize) -> usize {
+    let k = comb.len();
+    let mut rank = 0;
+    let mut prev = 0;
+    for (i, &c) in comb.iter().enumerate() {
+        for j in prev..c {
+            rank += value(n - j - 1, k - i - 1);
+        }
+        prev = c + 1;
+    }
+    rank
+}
+
+/// Shared greedy lex unranking, with `value(n, k)` supplying `C(n, k)`.
+fn unrank_by(mut rank: usize, n: usize, k: usize, value: impl Fn(usize, usize) -> usize) -> Vec<usize> {
     let mut comb = Vec::with_capacity(k);
     let mut prev = 0;
     for i in 0..k {
         for j in prev..n {
-            let c = binom(n - j - 1, k - i - 1);
+            let c = value(n - j - 1, k - i - 1);
             if rank < c {
                 comb.push(j);
                 prev = j + 1;
@@ -110,4 +151,20 @@ mod tests {
         let u = unrank_comb(r, 6, comb.len());
         assert_eq!(comb, u);
     }
+
+    #[test]
+    fn test_table_variants_match() {
+        let table = BinomialTable::new(70, 5);
+        for r in 0..binom(9, 4) {
+            let c = unrank_comb(r, 9, 4);
+            assert_eq!(unrank_comb_with(&table, r, 9, 4), c);
+            assert_eq!(rank_comb_with(&table, &c, 9), r);
+        }
+        // C(64, 32) fits in u64, but C(64, 31) · 33 does not
+        let table = BinomialTable::new(64, 32);
+        let last: Vec<usize> = (32..64).collect();
+        assert_eq!(binom(64, 32), 1832624140942590534);
+        assert_eq!(rank_comb(&last, 64), binom(64, 32) - 1);
+        assert_eq!(unrank_comb_with(&table, binom(64, 32) - 1, 64, 32), last);
+    }
 }
@@ -0,0 +1,244 @@
+//! # Chapter 5: Combinations — Reservoir and Weighted Sampling
+//!
+//! Sample k items without replacement when `n` is not known in advance
+//! (reservoir sampling) or when items carry non-uniform weights.
+//!
+//! ## Algorithm Description
+//!
+//! **Algorithm R** (Vitter): keep the first k items; item `i ≥ k` replaces a
+//!   uniformly chosen slot with probability `k / (i + 1)`.
+//! **Algorithm L** (Li): same distribution, but draws the *gap* to the next
+//!   replacement from a geometric distribution, so it skips over items instead
+//!   of drawing one random number per item.
+//! **Efraimidis–Spirakis (A-Res)**: give item `i` the key `u_i^(1/w_i)` with
+//!   `u_i` uniform in (0, 1) and keep the k largest keys. Compared in log space
+//!   (`ln(u_i) / w_i`) to avoid underflow for small weights.
+//!
+//! ## Complexity
+//!
+//! - Algorithm R: O(n) time, n random draws
+//! - Algorithm L: O(n) iteration, O(k (1 + log(n/k))) random draws
+//! - Weighted: O(n log k) time, O(k) space
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use rand::distributions::Open01;
+use rand::Rng;
+
+/// Uniform k-sample from an iterator of unknown length (Algorithm R).
+///
+/// Uses `rand::thread_rng()`; see [`reservoir_sample_with`] for a seedable
+/// variant.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::combinations::sampling::reservoir_sample;
+/// let s = reservoir_sample((0..100).filter(|x| x % 3 == 0), 5);
+/// assert_eq!(s.len(), 5);
+/// assert!(s.iter().all(|x| x % 3 == 0));
+/// ```
+pub fn reservoir_sample<I: IntoIterator>(iter: I, k: usize) -> Vec<I::Item> {
+    reservoir_sample_with(iter, k, &mut rand::thread_rng())
+}
+
+/// Uniform k-sample from an iterator of unknown length (Algorithm R) using the
+/// given RNG.
+///
+/// # Returns
+///
+/// The sampled items in reservoir order (not sorted). If the iterator yields
+/// fewer than `k` items, all of them are returned.
+pub fn reservoir_sample_with<I, R>(iter: I, k: usize, rng: &mut R) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    R: Rng + ?Sized,
+{
+    let mut iter = iter.into_iter();
+    let mut reservoir: Vec<I::Item> = iter.by_ref().take(k).collect();
+    if reservoir.len() < k {
+        return reservoir;
+    }
+    for (i, item) in iter.enumerate() {
+        let j = rng.gen_range(0..=k + i);
+        if j < k {
+            reservoir[j] = item;
+        }
+    }
+    reservoir
+}
+
+/// Uniform k-sample from an iterator of unknown length (Algorithm L).
+///
+/// Uses `rand::thread_rng()`; see [`reservoir_sample_skip_with`] for a
+/// seedable variant.
+pub fn reservoir_sample_skip<I: IntoIterator>(iter: I, k: usize) -> Vec<I::Item> {
+    reservoir_sample_skip_with(iter, k, &mut rand::thread_rng())
+}
+
+/// Uniform k-sample from an iterator of unknown length (Algorithm L) using the
+/// given RNG.
+///
+/// Skipped items are consumed with [`Iterator::nth`], so iterators with a cheap
+/// `nth` benefit the most.
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::combinations::sampling::reservoir_sample_skip_with;
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let s = reservoir_sample_skip_with(0..1_000_000, 3, &mut rng);
+/// assert_eq!(s.len(), 3);
+/// ```
+pub fn reservoir_sample_skip_with<I, R>(iter: I, k: usize, rng: &mut R) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    R: Rng + ?Sized,
+{
+    let mut iter = iter.into_iter();
+    let mut reservoir: Vec<I::Item> = iter.by_ref().take(k).collect();
+    if k == 0 || reservoir.len() < k {
+        return reservoir;
+    }
+    let kf = k as f64;
+    let mut w = (rng.sample::<f64, _>(Open01).ln() / kf).exp();
+    loop {
+        // ln(1 - w) loses every digit once w is below f64::EPSILON
+        let gap = (rng.sample::<f64, _>(Open01).ln() / (-w).ln_1p()).floor();
+        // a gap beyond usize::MAX means the stream ends first
+        let Some(item) = iter.nth(gap.min(usize::MAX as f64) as usize) else {
+            return reservoir;
+        };
+        reservoir[rng.gen_range(0..k)] = item;
+        w *= (rng.sample::<f64, _>(Open01).ln() / kf).exp();
+    }
+}
+
+/// Weighted k-subset of `0..weights.len()` without replacement
+/// (Efraimidis–Spirakis).
+///
+/// Uses `rand::thread_rng()`; see [`weighted_combination_with`] for a
+/// seedable variant.
+pub fn weighted_combination(weights: &[f64], k: usize) -> Vec<usize> {
+    weighted_combination_with(weights, k, &mut rand::thread_rng())
+}
+
+/// Weighted k-subset of `0..weights.len()` without replacement
+/// (Efraimidis–Spirakis) using the given RNG.
+///
+/// Item `i` is drawn first with probability `w_i / Σ w`, then the next from the
+/// remaining items in proportion to their weights, and so on. Zero-weight items
+/// are only chosen once every positive-weight item has been.
+///
+/// # Returns
+///
+/// The chosen indices, sorted.
+///
+/// # Panics
+///
+/// Panics if `k > weights.len()` or any weight is negative or NaN.
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::combinations::sampling::weighted_combination_with;
+/// let mut rng = StdRng::seed_from_u64(3);
+/// let c = weighted_combination_with(&[1.0, 0.0, 5.0, 2.0], 3, &mut rng);
+/// assert_eq!(c, vec![0, 2, 3]);
+/// ```
+pub fn weighted_combination_with<R: Rng + ?Sized>(
+    weights: &[f64],
+    k: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    assert!(k <= weights.len(), "cannot choose {} of {} elements", k, weights.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    // min-heap on key: the root is the weakest item kept so far
+    let mut heap: BinaryHeap<Reverse<Keyed>> = BinaryHeap::with_capacity(k + 1);
+    for (i, &w) in weights.iter().enumerate() {
+        assert!(w >= 0.0, "weight {} at index {} is not a non-negative number", w, i);
+        let key = rng.sample::<f64, _>(Open01).ln() / w;
+        heap.push(Reverse(Keyed(key, i)));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    let mut out: Vec<usize> = heap.into_iter().map(|Reverse(Keyed(_, i))| i).collect();
+    out.sort_unstable();
+    out
+}
+
+/// Sampling key paired with its index, ordered by key.
+struct Keyed(f64, usize);
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Keyed {}
+
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_reservoir_samples_are_roughly_uniform() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let (n, k, trials) = (10, 3, 20_000);
+        let mut hits_r = [0usize; 10];
+        let mut hits_l = [0usize; 10];
+        for _ in 0..trials {
+            for x in reservoir_sample_with(0..n, k, &mut rng) {
+                hits_r[x] += 1;
+            }
+            for x in reservoir_sample_skip_with(0..n, k, &mut rng) {
+                hits_l[x] += 1;
+            }
+        }
+        // expected 6000 per item
+        for h in hits_r.iter().chain(&hits_l) {
+            assert!((5600..6400).contains(h), "{:?} / {:?}", hits_r, hits_l);
+        }
+        assert_eq!(reservoir_sample_with(0..2, 5, &mut rng), vec![0, 1]);
+        assert_eq!(reservoir_sample_skip_with(0..2, 5, &mut rng), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_weighted_first_pick_is_proportional() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let weights = [1.0, 3.0, 0.0, 4.0];
+        let trials = 16_000;
+        let mut hits = [0usize; 4];
+        for _ in 0..trials {
+            for i in weighted_combination_with(&weights, 1, &mut rng) {
+                hits[i] += 1;
+            }
+        }
+        assert_eq!(hits[2], 0);
+        // expected 2000, 6000, 0, 8000
+        assert!((1700..2300).contains(&hits[0]), "{:?}", hits);
+        assert!((5600..6400).contains(&hits[1]), "{:?}", hits);
+        assert!((7600..8400).contains(&hits[3]), "{:?}", hits);
+        assert_eq!(weighted_combination_with(&weights, 4, &mut rng), vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,248 @@
+//! # Chapter 6: Compositions — Generation
+//!
+//! A **composition** of `n` is an ordered sequence of positive integers summing
+//! to `n` (a partition where order matters). Variants: exactly `k` parts,
+//! **weak** compositions (parts may be `0`), and parts bounded above.
+//!
+//! ## Algorithm Description
+//!
+//! **Stars and bars**: a composition of `n` into `k` parts is determined by
+//!   its partial sums `s₁ < s₂ < … < s_{k-1}` in `1..n`, i.e. a (k−1)-subset of
+//!   an (n−1)-set. [`composition_to_subset`] and [`subset_to_composition`]
+//!   implement this bijection; it maps lex order to lex order.
+//! **Weak compositions**: adding `1` to every part maps weak compositions of
+//!   `n` into `k` parts onto compositions of `n + k` into `k` parts.
+//! **Successor** ([`next_composition`]): bump the rightmost part that has a
+//!   later part greater than `1`, reset the parts after it to `1` and put the
+//!   remainder in the last part.
+//!
+//! All lists are returned in lexicographic order of the part sequences.
+//!
+//! ## Complexity
+//!
+//! - Successor and bijection: O(k)
+//! - Listing: O(k) per composition
+
+/// The (k−1)-subset of `0..n-1` encoding a composition into `k ≥ 1` parts:
+/// its partial sums, minus one, without the final total.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::generate::composition_to_subset;
+/// assert_eq!(composition_to_subset(&[2, 1, 3]), vec![1, 2]);
+/// ```
+pub fn composition_to_subset(comp: &[usize]) -> Vec<usize> {
+    let mut sum = 0;
+    comp.iter()
+        .take(comp.len().saturating_sub(1))
+        .map(|&x| {
+            sum += x;
+            sum - 1
+        })
+        .collect()
+}
+
+/// The composition of `n` encoded by a sorted subset of `0..n-1`, inverse of
+/// [`composition_to_subset`]. The composition has `subset.len() + 1` parts.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::generate::subset_to_composition;
+/// assert_eq!(subset_to_composition(&[1, 2], 6), vec![2, 1, 3]);
+/// ```
+pub fn subset_to_composition(subset: &[usize], n: usize) -> Vec<usize> {
+    let mut prev = 0;
+    subset
+        .iter()
+        .map(|&s| s + 1)
+        .chain(std::iter::once(n))
+        .map(|s| {
+            let part = s - prev;
+            prev = s;
+            part
+        })
+        .collect()
+}
+
+/// Advance a composition to its lex successor with the same sum and number of
+/// parts.
+///
+/// # Returns
+///
+/// `true` if advanced; `false` if `comp` was already the last one (`[n-k+1, 1, …, 1]`).
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::generate::next_composition;
+/// let mut c = vec![1, 3, 1];
+/// assert!(next_composition(&mut c));
+/// assert_eq!(c, vec![2, 1, 2]);
+/// ```
+pub fn next_composition(comp: &mut [usize]) -> bool {
+    let k = comp.len();
+    // rightmost i whose tail can give up one unit
+    let mut tail = 0;
+    for i in (0..k.saturating_sub(1)).rev() {
+        tail += comp[i + 1];
+        if tail > k - 1 - i {
+            comp[i] += 1;
+            comp[i + 1..].iter_mut().for_each(|x| *x = 1);
+            comp[k - 1] = tail - 1 - (k - 2 - i);
+            return true;
+        }
+    }
+    false
+}
+
+/// All compositions of `n`, in lex order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::generate::compositions;
+/// let all = compositions(3);
+/// assert_eq!(all, vec![vec![1, 1, 1], vec![1, 2], vec![2, 1], vec![3]]);
+/// ```
+pub fn compositions(n: usize) -> Vec<Vec<usize>> {
+    fn helper(rem: usize, current: &mut Vec<usize>, result: &mut Vec<Vec<usize>>) {
+        if rem == 0 {
+            result.push(current.clone());
+            return;
+        }
+        for part in 1..=rem {
+            current.push(part);
+            helper(rem - part, current, result);
+            current.pop();
+        }
+    }
+
+    let mut result = vec![];
+    helper(n, &mut Vec::new(), &mut result);
+    result
+}
+
+/// All compositions of `n` into exactly `k` parts, in lex order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::generate::compositions_k;
+/// assert_eq!(compositions_k(4, 2), vec![vec![1, 3], vec![2, 2], vec![3, 1]]);
+/// ```
+pub fn compositions_k(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k > n || (k == 0 && n > 0) {
+        return vec![];
+    }
+    let mut c = vec![1; k];
+    if k > 0 {
+        c[k - 1] = n - k + 1;
+    }
+    let mut result = vec![c.clone()];
+    while next_composition(&mut c) {
+        result.push(c.clone());
+    }
+    result
+}
+
+/// All weak compositions of `n` into exactly `k` parts (parts may be `0`), in
+/// lex order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::generate::weak_compositions;
+/// assert_eq!(weak_compositions(2, 2), vec![vec![0, 2], vec![1, 1], vec![2, 0]]);
+/// ```
+pub fn weak_compositions(n: usize, k: usize) -> Vec<Vec<usize>> {
+    compositions_k(n + k, k)
+        .into_iter()
+        .map(|c| c.into_iter().map(|x| x - 1).collect())
+        .collect()
+}
+
+/// All compositions of `n` into exactly `k` parts, each between `1` and
+/// `max_part`, in lex order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::generate::bounded_compositions;
+/// // three dice summing to 4
+/// assert_eq!(bounded_compositions(4, 3, 6), vec![vec![1, 1, 2], vec![1, 2, 1], vec![2, 1, 1]]);
+/// ```
+pub fn bounded_compositions(n: usize, k: usize, max_part: usize) -> Vec<Vec<usize>> {
+    fn helper(
+        rem: usize,
+        slots: usize,
+        max_part: usize,
+        current: &mut Vec<usize>,
+        result: &mut Vec<Vec<usize>>,
+    ) {
+        if slots == 0 {
+            if rem == 0 {
+                result.push(current.clone());
+            }
+            return;
+        }
+        // leave at least 1 and at most `max_part` for each later slot
+        let lo = rem.saturating_sub((slots - 1) * max_part).max(1);
+        let hi = max_part.min(rem.saturating_sub(slots - 1));
+        for part in lo..=hi {
+            current.push(part);
+            helper(rem - part, slots - 1, max_part, current, result);
+            current.pop();
+        }
+    }
+
+    let mut result = vec![];
+    helper(n, k, max_part, &mut Vec::new(), &mut result);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinations::lex::all_combinations;
+
+    #[test]
+    fn test_subset_bijection_preserves_lex_order() {
+        for n in 1..=8 {
+            for k in 1..=n {
+                let from_subsets: Vec<Vec<usize>> = all_combinations(n - 1, k - 1)
+                    .iter()
+                    .map(|s| subset_to_composition(s, n))
+                    .collect();
+                let comps = compositions_k(n, k);
+                assert_eq!(comps, from_subsets);
+                assert!(comps.iter().all(|c| subset_to_composition(&composition_to_subset(c), n) == *c));
+            }
+            let mut by_k: Vec<Vec<usize>> = (1..=n).flat_map(|k| compositions_k(n, k)).collect();
+            by_k.sort();
+            assert_eq!(compositions(n), by_k);
+            assert_eq!(compositions(n).len(), 1 << (n - 1));
+        }
+    }
+
+    #[test]
+    fn test_weak_and_bounded_match_filters() {
+        for n in 0..=7 {
+            for k in 0..=4 {
+                let weak = weak_compositions(n, k);
+                assert!(weak.iter().all(|c| c.len() == k && c.iter().sum::<usize>() == n));
+                assert!(weak.windows(2).all(|w| w[0] < w[1]));
+                for m in 1..=4 {
+                    let b: Vec<Vec<usize>> = compositions_k(n, k)
+                        .into_iter()
+                        .filter(|c| c.iter().all(|&x| x <= m))
+                        .collect();
+                    assert_eq!(bounded_compositions(n, k, m), b);
+                }
+            }
+        }
+        assert_eq!(weak_compositions(0, 0), vec![Vec::<usize>::new()]);
+        assert_eq!(weak_compositions(3, 0), Vec::<Vec<usize>>::new());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod generate;
+pub mod random;
+pub mod rank;
@@ -0,0 +1,162 @@
+//! # Chapter 6: Compositions — Random Generation
+//!
+//! Sample compositions uniformly at random.
+//!
+//! ## Algorithm Description
+//!
+//! - **All compositions of n**: each of the `n − 1` gaps between consecutive
+//!   units is a cut with probability `1/2`, independently.
+//! - **Exactly k parts / weak**: draw a uniform (k−1)-subset with
+//!   [`random_combination_with`] and map it through stars and bars.
+//! - **Bounded parts**: draw a uniform rank and unrank it.
+//!
+//! ## Complexity
+//!
+//! - All: O(n)
+//! - Exactly k / weak: O(k log k)
+//! - Bounded: O(k · n · max)
+
+use rand::Rng;
+
+use crate::combinations::random::random_combination_with;
+
+use super::generate::subset_to_composition;
+use super::rank::{count_bounded_compositions, unrank_bounded_composition};
+
+/// Uniformly random composition of `n`.
+///
+/// Uses `rand::thread_rng()`; see [`random_composition_with`] for a seedable
+/// variant.
+pub fn random_composition(n: usize) -> Vec<usize> {
+    random_composition_with(n, &mut rand::thread_rng())
+}
+
+/// Uniformly random composition of `n` using the given RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::compositions::random::random_composition_with;
+/// let c = random_composition_with(10, &mut StdRng::seed_from_u64(4));
+/// assert_eq!(c.iter().sum::<usize>(), 10);
+/// ```
+pub fn random_composition_with<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    let cuts: Vec<usize> = (0..n.saturating_sub(1)).filter(|_| rng.gen_bool(0.5)).collect();
+    match n {
+        0 => Vec::new(),
+        _ => subset_to_composition(&cuts, n),
+    }
+}
+
+/// Uniformly random composition of `n` into exactly `k` parts.
+///
+/// Uses `rand::thread_rng()`; see [`random_composition_k_with`] for a seedable
+/// variant.
+pub fn random_composition_k(n: usize, k: usize) -> Vec<usize> {
+    random_composition_k_with(n, k, &mut rand::thread_rng())
+}
+
+/// Uniformly random composition of `n` into exactly `k` parts using the given
+/// RNG.
+///
+/// # Panics
+///
+/// Panics if there is no such composition (`k > n`, or `k == 0 < n`).
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::compositions::random::random_composition_k_with;
+/// let c = random_composition_k_with(20, 4, &mut StdRng::seed_from_u64(4));
+/// assert_eq!(c.len(), 4);
+/// assert_eq!(c.iter().sum::<usize>(), 20);
+/// ```
+pub fn random_composition_k_with<R: Rng + ?Sized>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    assert!(k <= n && (k > 0 || n == 0), "no composition of {} into {} parts", n, k);
+    match n {
+        0 => Vec::new(),
+        _ => subset_to_composition(&random_combination_with(n - 1, k - 1, rng), n),
+    }
+}
+
+/// Uniformly random weak composition of `n` into exactly `k` parts.
+///
+/// Uses `rand::thread_rng()`; see [`random_weak_composition_with`] for a
+/// seedable variant.
+pub fn random_weak_composition(n: usize, k: usize) -> Vec<usize> {
+    random_weak_composition_with(n, k, &mut rand::thread_rng())
+}
+
+/// Uniformly random weak composition of `n` into exactly `k` parts using the
+/// given RNG.
+///
+/// # Panics
+///
+/// Panics if `k == 0 < n`.
+pub fn random_weak_composition_with<R: Rng + ?Sized>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    random_composition_k_with(n + k, k, rng).into_iter().map(|x| x - 1).collect()
+}
+
+/// Uniformly random composition of `n` into exactly `k` parts in
+/// `1..=max_part`.
+///
+/// Uses `rand::thread_rng()`; see [`random_bounded_composition_with`] for a
+/// seedable variant.
+pub fn random_bounded_composition(n: usize, k: usize, max_part: usize) -> Vec<usize> {
+    random_bounded_composition_with(n, k, max_part, &mut rand::thread_rng())
+}
+
+/// Uniformly random composition of `n` into exactly `k` parts in
+/// `1..=max_part` using the given RNG.
+///
+/// # Panics
+///
+/// Panics if there is no such composition, or if their number does not fit
+/// in `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::compositions::random::random_bounded_composition_with;
+/// let dice = random_bounded_composition_with(10, 3, 6, &mut StdRng::seed_from_u64(4));
+/// assert!(dice.iter().all(|&d| (1..=6).contains(&d)));
+/// ```
+pub fn random_bounded_composition_with<R: Rng + ?Sized>(
+    n: usize,
+    k: usize,
+    max_part: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    let total = count_bounded_compositions(n, k, max_part);
+    assert!(total > 0, "no composition of {} into {} parts of size at most {}", n, k, max_part);
+    unrank_bounded_composition(rng.gen_range(0..total), n, k, max_part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositions::rank::{rank_composition, rank_composition_k};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_samplers_are_roughly_uniform() {
+        let mut rng = StdRng::seed_from_u64(22);
+        // 2^4 = 16 compositions of 5, 6 of 5 into 3 parts; 1000 expected hits
+        let mut all = [0usize; 16];
+        let mut three = [0usize; 6];
+        for _ in 0..16_000 {
+            all[rank_composition(&random_composition_with(5, &mut rng))] += 1;
+        }
+        for _ in 0..6000 {
+            three[rank_composition_k(&random_composition_k_with(5, 3, &mut rng))] += 1;
+        }
+        for h in all.iter().chain(&three) {
+            assert!((870..1130).contains(h), "{:?} / {:?}", all, three);
+        }
+        let w = random_weak_composition_with(0, 3, &mut rng);
+        assert_eq!(w, vec![0, 0, 0]);
+    }
+}
@@ -0,0 +1,311 @@
+//! # Chapter 6: Compositions — Counting and Ranking
+//!
+//! Count, rank and unrank compositions in the lex orders produced by
+//! [`generate`](super::generate).
+//!
+//! ## Algorithm Description
+//!
+//! **Exactly k parts**: rank the stars-and-bars subset with
+//!   [`rank_comb`]; weak compositions reuse this after adding `1` to each part.
+//! **All compositions**: there are `2^{m−1}` compositions of `m ≥ 1`, so the
+//!   rank adds `2^{rem−v−1}` (or `1` when `v = rem`) for every value `v` below
+//!   each part.
+//! **Bounded parts**: `B[j][m]` = compositions of `m` into `j` parts in
+//!   `1..=max`; the rank adds `B[j−1][rem − v]` for every smaller value `v`.
+//!   Row `j` overflows well before row `j − 1` does: 27 six-sided dice can
+//!   total 83 in more than `2^64` ways, while every count for 26 dice fits.
+//!   Such entries are `None`, and unranking still places any `usize` rank
+//!   with [`find_block`].
+//!
+//! ## Complexity
+//!
+//! - Exactly k / weak: as [`rank_comb`] on an (n−1)-set
+//! - All: O(n)
+//! - Bounded: O(k · n · max) table, O(k · max) rank / unrank
+
+use crate::arith::rank_int::find_block;
+use crate::combinations::ranking::{binom, rank_comb, unrank_comb};
+
+use super::generate::{composition_to_subset, subset_to_composition};
+
+/// Number of compositions of `n`: `2^{n−1}` (and `1` for `n = 0`).
+///
+/// # Panics
+///
+/// Panics if the count does not fit in `usize`.
+pub fn count_compositions(n: usize) -> usize {
+    match n {
+        0 => 1,
+        _ => 1usize.checked_shl(n as u32 - 1).expect("composition count overflows usize"),
+    }
+}
+
+/// Number of compositions of `n` into exactly `k` parts, `C(n−1, k−1)`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::rank::count_compositions_k;
+/// assert_eq!(count_compositions_k(6, 3), 10);
+/// ```
+pub fn count_compositions_k(n: usize, k: usize) -> usize {
+    match (n, k) {
+        (0, 0) => 1,
+        (_, 0) | (0, _) => 0,
+        _ => binom(n - 1, k - 1),
+    }
+}
+
+/// Number of weak compositions of `n` into exactly `k` parts, `C(n+k−1, k−1)`.
+pub fn count_weak_compositions(n: usize, k: usize) -> usize {
+    count_compositions_k(n + k, k)
+}
+
+/// Number of compositions of `n` into exactly `k` parts in `1..=max_part`.
+///
+/// # Panics
+///
+/// Panics if the count does not fit in `usize`; see
+/// [`checked_count_bounded_compositions`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::rank::count_bounded_compositions;
+/// // ways for two dice to show a total of 7
+/// assert_eq!(count_bounded_compositions(7, 2, 6), 6);
+/// ```
+pub fn count_bounded_compositions(n: usize, k: usize, max_part: usize) -> usize {
+    checked_count_bounded_compositions(n, k, max_part)
+        .expect("bounded composition count overflows usize")
+}
+
+/// Number of compositions of `n` into exactly `k` parts in `1..=max_part`, or
+/// `None` if it does not fit in `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::rank::checked_count_bounded_compositions;
+/// assert_eq!(checked_count_bounded_compositions(130, 130, 130), Some(1));
+/// assert_eq!(checked_count_bounded_compositions(130, 65, 130), None);
+/// ```
+pub fn checked_count_bounded_compositions(n: usize, k: usize, max_part: usize) -> Option<usize> {
+    bounded_table(n, k, max_part)[k][n]
+}
+
+/// Lex rank of a composition among all compositions of its sum.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::rank::{rank_composition, unrank_composition};
+/// assert_eq!(rank_composition(&[1, 2]), 1);
+/// assert_eq!(unrank_composition(2, 3), vec![2, 1]);
+/// ```
+pub fn rank_composition(comp: &[usize]) -> usize {
+    let mut rem: usize = comp.iter().sum();
+    let mut rank = 0;
+    for &x in comp {
+        rank += (1..x).map(|v| count_compositions(rem - v)).sum::<usize>();
+        rem -= x;
+    }
+    rank
+}
+
+/// The composition of `n` with the given lex rank among all compositions.
+///
+/// # Panics
+///
+/// Panics if `rank ≥ 2^{n−1}`.
+pub fn unrank_composition(mut rank: usize, n: usize) -> Vec<usize> {
+    assert!(rank < count_compositions(n), "rank {} out of range", rank);
+    let mut comp = Vec::new();
+    let mut rem = n;
+    while rem > 0 {
+        let mut v = 1;
+        while rank >= count_compositions(rem - v) {
+            rank -= count_compositions(rem - v);
+            v += 1;
+        }
+        comp.push(v);
+        rem -= v;
+    }
+    comp
+}
+
+/// Lex rank of a composition among the compositions of its sum with the same
+/// number of parts.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::rank::{rank_composition_k, unrank_composition_k};
+/// assert_eq!(rank_composition_k(&[2, 1, 3]), 4);
+/// assert_eq!(unrank_composition_k(4, 6, 3), vec![2, 1, 3]);
+/// ```
+pub fn rank_composition_k(comp: &[usize]) -> usize {
+    let n: usize = comp.iter().sum();
+    match n {
+        0 => 0,
+        _ => rank_comb(&composition_to_subset(comp), n - 1),
+    }
+}
+
+/// The composition of `n` into `k` parts with the given lex rank.
+///
+/// # Panics
+///
+/// Panics if `rank ≥ C(n−1, k−1)`.
+pub fn unrank_composition_k(rank: usize, n: usize, k: usize) -> Vec<usize> {
+    assert!(rank < count_compositions_k(n, k), "rank {} out of range", rank);
+    match n {
+        0 => Vec::new(),
+        _ => subset_to_composition(&unrank_comb(rank, n - 1, k - 1), n),
+    }
+}
+
+/// Lex rank of a weak composition among those with the same sum and length.
+pub fn rank_weak_composition(comp: &[usize]) -> usize {
+    let shifted: Vec<usize> = comp.iter().map(|&x| x + 1).collect();
+    rank_composition_k(&shifted)
+}
+
+/// The weak composition of `n` into `k` parts with the given lex rank.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::rank::{rank_weak_composition, unrank_weak_composition};
+/// assert_eq!(unrank_weak_composition(1, 2, 2), vec![1, 1]);
+/// assert_eq!(rank_weak_composition(&[1, 1]), 1);
+/// ```
+pub fn unrank_weak_composition(rank: usize, n: usize, k: usize) -> Vec<usize> {
+    unrank_composition_k(rank, n + k, k).into_iter().map(|x| x - 1).collect()
+}
+
+/// Lex rank of a composition among those with the same sum and length whose
+/// parts are all at most `max_part`.
+///
+/// # Panics
+///
+/// Panics if the rank does not fit in `usize`.
+pub fn rank_bounded_composition(comp: &[usize], max_part: usize) -> usize {
+    let n: usize = comp.iter().sum();
+    let k = comp.len();
+    let b = bounded_table(n, k, max_part);
+    let mut rem = n;
+    let mut rank = 0usize;
+    for (i, &x) in comp.iter().enumerate() {
+        let slots = k - 1 - i;
+        for v in 1..x.min(rem + 1) {
+            rank = b[slots][rem - v]
+                .and_then(|block| rank.checked_add(block))
+                .expect("bounded composition rank overflows usize");
+        }
+        rem -= x;
+    }
+    rank
+}
+
+/// The composition of `n` into `k` parts in `1..=max_part` with the given lex
+/// rank.
+///
+/// # Panics
+///
+/// Panics if the rank is out of range.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::compositions::rank::{rank_bounded_composition, unrank_bounded_composition};
+/// let c = unrank_bounded_composition(3, 7, 2, 6);
+/// assert_eq!(c, vec![4, 3]);
+/// assert_eq!(rank_bounded_composition(&c, 6), 3);
+/// ```
+pub fn unrank_bounded_composition(mut rank: usize, n: usize, k: usize, max_part: usize) -> Vec<usize> {
+    let b = bounded_table(n, k, max_part);
+    assert!(b[k][n].is_none_or(|total| rank < total), "rank {} out of range", rank);
+    let mut comp = Vec::with_capacity(k);
+    let mut rem = n;
+    for slots in (0..k).rev() {
+        let blocks = (1..=max_part.min(rem)).map(|v| b[slots][rem - v].as_ref());
+        let v = 1 + find_block(&mut rank, blocks).expect("rank out of range");
+        comp.push(v);
+        rem -= v;
+    }
+    comp
+}
+
+/// `b[j][m]` = compositions of `m ≤ n` into `j ≤ k` parts in `1..=max_part`,
+/// or `None` if that number does not fit in `usize`.
+fn bounded_table(n: usize, k: usize, max_part: usize) -> Vec<Vec<Option<usize>>> {
+    let mut b = vec![vec![Some(0usize); n + 1]; k + 1];
+    b[0][0] = Some(1);
+    for j in 1..=k {
+        for m in 1..=n {
+            b[j][m] = (1..=max_part.min(m))
+                .try_fold(0usize, |acc, v| acc.checked_add(b[j - 1][m - v]?));
+        }
+    }
+    b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositions::generate::{
+        bounded_compositions, compositions, compositions_k, weak_compositions,
+    };
+
+    #[test]
+    fn test_rank_unrank_roundtrips() {
+        for n in 0..=8 {
+            let all = compositions(n);
+            assert_eq!(all.len(), count_compositions(n));
+            for (r, c) in all.iter().enumerate() {
+                assert_eq!(rank_composition(c), r);
+                assert_eq!(unrank_composition(r, n), *c);
+            }
+            for k in 0..=n {
+                let ck = compositions_k(n, k);
+                assert_eq!(ck.len(), count_compositions_k(n, k));
+                for (r, c) in ck.iter().enumerate() {
+                    assert_eq!(rank_composition_k(c), r);
+                    assert_eq!(unrank_composition_k(r, n, k), *c);
+                }
+                let wk = weak_compositions(n, k);
+                assert_eq!(wk.len(), count_weak_compositions(n, k));
+                for (r, c) in wk.iter().enumerate() {
+                    assert_eq!(rank_weak_composition(c), r);
+                    assert_eq!(unrank_weak_composition(r, n, k), *c);
+                }
+                for m in 1..=3 {
+                    let bk = bounded_compositions(n, k, m);
+                    assert_eq!(bk.len(), count_bounded_compositions(n, k, m));
+                    for (r, c) in bk.iter().enumerate() {
+                        assert_eq!(rank_bounded_composition(c, m), r);
+                        assert_eq!(unrank_bounded_composition(r, n, k, m), *c);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounded_ranks_of_many_dice() {
+        // 27 dice: totals up to 82 have fewer than 2^64 outcomes, 83 has more
+        let total = count_bounded_compositions(82, 27, 6);
+        assert_eq!(total, 17229736168152208056);
+        let last: Vec<usize> = [6; 11].into_iter().chain([1; 16]).collect();
+        assert_eq!(rank_bounded_composition(&last, 6), total - 1);
+        assert_eq!(unrank_bounded_composition(total - 1, 82, 27, 6), last);
+        assert_eq!(checked_count_bounded_compositions(83, 27, 6), None);
+        // only the total overflows, so every usize rank still unranks
+        let first: Vec<usize> = [1; 15].into_iter().chain([2]).chain([6; 11]).collect();
+        assert_eq!(unrank_bounded_composition(0, 83, 27, 6), first);
+        let c = unrank_bounded_composition(usize::MAX, 83, 27, 6);
+        assert_eq!(c.iter().sum::<usize>(), 83);
+        assert_eq!(rank_bounded_composition(&c, 6), usize::MAX);
+    }
+}
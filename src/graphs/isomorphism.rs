@@ -12,7 +12,7 @@
 //!
 //! - Time: O(n! · n²)
 
-use crate::permutations::lex::next_lex_perm;
+use crate::permutations::lex::LexPermutations;
 
 /// Test whether two adjacency matrices represent isomorphic graphs.
 ///
@@ -35,22 +35,11 @@ use crate::permutations::lex::next_lex_perm;
 pub fn are_isomorphic(g1: &[Vec<bool>], g2: &[Vec<bool>]) -> bool {
     let n = g1.len();
     if n != g2.len() { return false; }
-    let mut perm: Vec<usize> = (0..n).collect();
-    loop {
-        let mut match_all = true;
-        for i in 0..n {
-            for j in 0..n {
-                if g1[i][j] != g2[perm[i]][perm[j]] {
-                    match_all = false;
-                    break;
-                }
-            }
-            if !match_all { break; }
-        }
+    let ids: Vec<usize> = (0..n).collect();
+    let mut perms = LexPermutations::new(&ids);
+    while let Some(perm) = perms.next_slice() {
+        let match_all = (0..n).all(|i| (0..n).all(|j| g1[i][j] == g2[perm[i]][perm[j]]));
         if match_all { return true; }
-        if !next_lex_perm(&mut perm) {
-            break;
-        }
     }
     false
 }
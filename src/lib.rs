@@ -1,5 +1,7 @@
+pub mod arith;
 pub mod backtrack;
 pub mod combinations;
+pub mod compositions;
 pub mod graphs;
 pub mod gray;
 pub mod partitions;
@@ -1,3 +1,4 @@
 pub mod fixed_k;
 pub mod generate_set;
-pub mod rank_set;
\ No newline at end of file
+pub mod rank_set;
+pub mod rgs;
\ No newline at end of file
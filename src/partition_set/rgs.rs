@@ -0,0 +1,429 @@
+//! # Chapter 7: Set Partitions — Restricted Growth Strings
+//!
+//! Represent a partition of `{0,1,…,n-1}` by its restricted growth string
+//! (RGS): `a[i]` is the block of element `i`, with blocks numbered in order of
+//! their smallest element. Such strings are exactly those with `a[0] = 0` and
+//! `a[i] ≤ 1 + max(a[0..i])`, so each set partition has one canonical form.
+//!
+//! ## Algorithm Description
+//!
+//! - **Lexicographic order** (Knuth's Algorithm H): keep `b[i] = 1 + max(a[0..i])`.
+//!   Find the largest `j` with `a[j] < b[j]`, increase `a[j]`, then reset every
+//!   later digit to 0 and set its bound to `b[j]`, or `b[j] + 1` if `a[j]` has
+//!   just opened a new block.
+//! - **Gray order** (Ruskey–Savage): with `m = max(a[0..j])`, digit `j` sweeps
+//!   either `0, m+1, m, …, 1` or `1, …, m, m+1, 0`, like the digits of a
+//!   reflected mixed-radix Gray code. Each step advances the rightmost digit
+//!   not yet at the end of its sweep and reverses the sweeps of the digits to
+//!   its right. Those digits sit at 0 or 1, which stays valid whatever the
+//!   prefix, so exactly one element changes block per step.
+//!
+//! ## Complexity
+//!
+//! - Time: O(B(n)) total for both orders (amortized O(1) per step), O(n)
+//!   worst case per step
+//! - Space: O(n)
+
+use std::fmt;
+use std::ops::Deref;
+
+/// Errors produced when constructing a [`SetPartition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetPartitionError {
+    /// The string does not start at 0 or jumps more than one past its maximum.
+    NotRestrictedGrowth,
+    /// The blocks are empty, overlap, or do not cover `0..n`.
+    NotABlockList,
+}
+
+impl fmt::Display for SetPartitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetPartitionError::NotRestrictedGrowth => f.write_str("not a restricted growth string"),
+            SetPartitionError::NotABlockList => f.write_str("blocks do not partition 0..n"),
+        }
+    }
+}
+
+impl std::error::Error for SetPartitionError {}
+
+/// A partition of `{0,1,…,n-1}` stored as its restricted growth string.
+///
+/// Ordering compares the strings lexicographically, which is the order of
+/// [`LexSetPartitions`]. Dereferences to `[usize]`, so `p[i]` is the block of
+/// element `i`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partition_set::rgs::SetPartition;
+/// let p = SetPartition::from_blocks(&[vec![1, 3], vec![0], vec![2]]).unwrap();
+/// assert_eq!(p.as_slice(), &[0, 1, 2, 1]);
+/// assert_eq!(p.blocks(), vec![vec![0], vec![1, 3], vec![2]]);
+/// assert_eq!(p.num_blocks(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SetPartition(Vec<usize>);
+
+impl SetPartition {
+    /// Validate `rgs` as a restricted growth string.
+    pub fn new(rgs: Vec<usize>) -> Result<Self, SetPartitionError> {
+        let mut bound = 0;
+        for &x in &rgs {
+            if x > bound {
+                return Err(SetPartitionError::NotRestrictedGrowth);
+            }
+            bound = bound.max(x + 1);
+        }
+        Ok(SetPartition(rgs))
+    }
+
+    /// Build the partition with the given blocks, in any order.
+    ///
+    /// # Returns
+    ///
+    /// `Err(SetPartitionError::NotABlockList)` unless the blocks are nonempty
+    /// and together contain each of `0..n` exactly once.
+    pub fn from_blocks<B: AsRef<[usize]>>(blocks: &[B]) -> Result<Self, SetPartitionError> {
+        let n = blocks.iter().map(|b| b.as_ref().len()).sum();
+        let mut label = vec![usize::MAX; n];
+        for (k, block) in blocks.iter().enumerate() {
+            let block = block.as_ref();
+            if block.is_empty() {
+                return Err(SetPartitionError::NotABlockList);
+            }
+            for &x in block {
+                match label.get_mut(x) {
+                    Some(l) if *l == usize::MAX => *l = k,
+                    _ => return Err(SetPartitionError::NotABlockList),
+                }
+            }
+        }
+        // Renumber blocks by first appearance.
+        let mut renamed = vec![usize::MAX; blocks.len()];
+        let mut next = 0;
+        for l in label.iter_mut() {
+            if renamed[*l] == usize::MAX {
+                renamed[*l] = next;
+                next += 1;
+            }
+            *l = renamed[*l];
+        }
+        Ok(SetPartition(label))
+    }
+
+    /// The restricted growth string as a slice.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// The restricted growth string as an owned vector.
+    pub fn into_vec(self) -> Vec<usize> {
+        self.0
+    }
+
+    /// The number of blocks.
+    pub fn num_blocks(&self) -> usize {
+        self.0.iter().max().map_or(0, |&m| m + 1)
+    }
+
+    /// The blocks, ordered by smallest element, each in increasing order.
+    ///
+    /// This is the layout produced by
+    /// [`generate_set_partitions`](super::generate_set::generate_set_partitions).
+    pub fn blocks(&self) -> Vec<Vec<usize>> {
+        let mut blocks = vec![Vec::new(); self.num_blocks()];
+        for (i, &b) in self.0.iter().enumerate() {
+            blocks[b].push(i);
+        }
+        blocks
+    }
+}
+
+impl Deref for SetPartition {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl AsRef<[usize]> for SetPartition {
+    fn as_ref(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<usize>> for SetPartition {
+    type Error = SetPartitionError;
+
+    fn try_from(v: Vec<usize>) -> Result<Self, Self::Error> {
+        SetPartition::new(v)
+    }
+}
+
+impl From<SetPartition> for Vec<usize> {
+    fn from(p: SetPartition) -> Vec<usize> {
+        p.0
+    }
+}
+
+/// The Bell number `B(n)`, or `None` if it does not fit in `usize`.
+fn bell_number(n: usize) -> Option<usize> {
+    if n == 0 {
+        return Some(1);
+    }
+    // Bell triangle: each row starts with the last entry of the previous one,
+    // and row i ends with B(i + 1).
+    let mut row = vec![1usize];
+    for _ in 1..n {
+        let mut next = vec![*row.last()?];
+        for &x in &row {
+            next.push(next.last()?.checked_add(x)?);
+        }
+        row = next;
+    }
+    row.last().copied()
+}
+
+/// Lazy iterator over the set partitions of `{0,1,…,n-1}` in lexicographic
+/// RGS order.
+///
+/// Yields owned [`SetPartition`] values through [`Iterator`], or the string
+/// itself through the streaming [`LexSetPartitions::next_slice`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partition_set::rgs::LexSetPartitions;
+/// let all: Vec<Vec<usize>> = LexSetPartitions::new(3).map(Vec::from).collect();
+/// assert_eq!(all, vec![
+///     vec![0, 0, 0],
+///     vec![0, 0, 1],
+///     vec![0, 1, 0],
+///     vec![0, 1, 1],
+///     vec![0, 1, 2],
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LexSetPartitions {
+    a: Vec<usize>,
+    // b[i] = 1 + max(a[0..i]), the largest value a[i] may take
+    b: Vec<usize>,
+    started: bool,
+    done: bool,
+    remaining: Option<usize>,
+}
+
+impl LexSetPartitions {
+    /// Start at the one-block partition `0 0 … 0`.
+    pub fn new(n: usize) -> Self {
+        LexSetPartitions {
+            a: vec![0; n],
+            b: vec![1; n],
+            started: false,
+            done: false,
+            remaining: bell_number(n),
+        }
+    }
+
+    /// Advance to the next string and lend it as a slice.
+    pub fn next_slice(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
+        if self.started {
+            let Some(j) = (1..self.a.len()).rev().find(|&j| self.a[j] < self.b[j]) else {
+                self.done = true;
+                return None;
+            };
+            self.a[j] += 1;
+            let bound = if self.a[j] == self.b[j] { self.b[j] + 1 } else { self.b[j] };
+            for i in j + 1..self.a.len() {
+                self.a[i] = 0;
+                self.b[i] = bound;
+            }
+        }
+        self.started = true;
+        if let Some(r) = self.remaining.as_mut() {
+            *r -= 1;
+        }
+        Some(&self.a)
+    }
+}
+
+impl Iterator for LexSetPartitions {
+    type Item = SetPartition;
+
+    fn next(&mut self) -> Option<SetPartition> {
+        self.next_slice().map(|a| SetPartition(a.to_vec()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match self.remaining {
+            Some(r) => (r, Some(r)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Lazy iterator over the set partitions of `{0,1,…,n-1}` in Gray order:
+/// successive partitions differ by moving a single element to another block.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partition_set::rgs::GraySetPartitions;
+/// let mut it = GraySetPartitions::new(3);
+/// assert_eq!(it.current(), &[0, 0, 0]);
+/// assert_eq!(it.next_change(), Some((2, 1))); // element 2 moves to block 1
+/// let rest: Vec<Vec<usize>> = it.map(Vec::from).collect();
+/// assert_eq!(rest, vec![vec![0, 1, 1], vec![0, 1, 2], vec![0, 1, 0]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraySetPartitions {
+    a: Vec<usize>,
+    // m[i] = max(a[0..i]), fixed while digit i is inside a sweep
+    m: Vec<usize>,
+    // whether digit i sweeps 0, m+1, m, …, 1 (rather than 1, …, m+1, 0)
+    down: Vec<bool>,
+    started: bool,
+    done: bool,
+    remaining: Option<usize>,
+}
+
+impl GraySetPartitions {
+    /// Start at the one-block partition `0 0 … 0`.
+    pub fn new(n: usize) -> Self {
+        GraySetPartitions {
+            a: vec![0; n],
+            m: vec![0; n],
+            down: vec![true; n],
+            started: false,
+            done: false,
+            remaining: bell_number(n),
+        }
+    }
+
+    /// The current restricted growth string.
+    pub fn current(&self) -> &[usize] {
+        &self.a
+    }
+
+    /// Advance by moving one element.
+    ///
+    /// # Returns
+    ///
+    /// `Some((i, b))` if element `i` moved to block `b`, or `None` once all
+    /// `B(n)` partitions have been visited. The starting partition counts as
+    /// visited, so the first call moves to the second partition.
+    pub fn next_change(&mut self) -> Option<(usize, usize)> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            self.tick();
+        }
+        let at_end = |j: usize| self.a[j] == usize::from(self.down[j]);
+        let Some(j) = (1..self.a.len()).rev().find(|&j| !at_end(j)) else {
+            self.done = true;
+            return None;
+        };
+        let top = self.m[j] + 1;
+        self.a[j] = match (self.down[j], self.a[j]) {
+            (true, 0) => top,
+            (true, x) => x - 1,
+            (false, x) if x == top => 0,
+            (false, x) => x + 1,
+        };
+        // Digits right of j sit at the ends of their sweeps and turn around.
+        for i in j + 1..self.a.len() {
+            self.down[i] = !self.down[i];
+            self.m[i] = self.m[i - 1].max(self.a[i - 1]);
+        }
+        self.tick();
+        Some((j, self.a[j]))
+    }
+
+    fn tick(&mut self) {
+        if let Some(r) = self.remaining.as_mut() {
+            *r -= 1;
+        }
+    }
+}
+
+impl Iterator for GraySetPartitions {
+    type Item = SetPartition;
+
+    fn next(&mut self) -> Option<SetPartition> {
+        if !self.started {
+            self.started = true;
+            self.tick();
+            return Some(SetPartition(self.a.clone()));
+        }
+        self.next_change().map(|_| SetPartition(self.a.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match self.remaining {
+            Some(r) => (r, Some(r)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partition_set::generate_set::generate_set_partitions;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_blocks_roundtrip_and_validation() {
+        let p = SetPartition::new(vec![0, 1, 0, 2, 1]).unwrap();
+        assert_eq!(p.blocks(), vec![vec![0, 2], vec![1, 4], vec![3]]);
+        assert_eq!(SetPartition::from_blocks(&p.blocks()), Ok(p));
+        assert_eq!(SetPartition::new(vec![1, 0]), Err(SetPartitionError::NotRestrictedGrowth));
+        assert_eq!(SetPartition::new(vec![0, 2]), Err(SetPartitionError::NotRestrictedGrowth));
+        let bad: [&[usize]; 3] = [&[0, 1], &[1], &[2]];
+        assert_eq!(SetPartition::from_blocks(&bad), Err(SetPartitionError::NotABlockList));
+        let gap: [&[usize]; 2] = [&[0, 3], &[1]];
+        assert_eq!(SetPartition::from_blocks(&gap), Err(SetPartitionError::NotABlockList));
+        assert_eq!(SetPartition::from_blocks::<Vec<usize>>(&[]).unwrap().num_blocks(), 0);
+    }
+
+    #[test]
+    fn test_lex_order_matches_generation() {
+        for n in 0..=8 {
+            let it = LexSetPartitions::new(n);
+            let expected = generate_set_partitions(n);
+            assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+            let all: Vec<SetPartition> = it.collect();
+            assert!(all.windows(2).all(|w| w[0] < w[1]));
+            let blocks: Vec<Vec<Vec<usize>>> = all.iter().map(SetPartition::blocks).collect();
+            assert_eq!(blocks, expected);
+        }
+        assert_eq!(bell_number(25), Some(4_638_590_332_229_999_353));
+        assert_eq!(bell_number(26), None);
+    }
+
+    #[test]
+    fn test_gray_order_moves_one_element() {
+        for n in 0..=9 {
+            let all: Vec<SetPartition> = GraySetPartitions::new(n).collect();
+            assert_eq!(all.len(), bell_number(n).unwrap());
+            let moved = |w: &[SetPartition]| w[0].iter().zip(w[1].iter()).filter(|(x, y)| x != y).count();
+            assert!(all.windows(2).all(|w| moved(w) == 1));
+            let distinct: HashSet<&SetPartition> = all.iter().collect();
+            assert_eq!(distinct.len(), all.len());
+            assert!(all.iter().all(|p| SetPartition::new(p.to_vec()).is_ok()));
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! # Chapter 6: Integer Partitions — Counting
+//!
+//! Exact partition numbers for `n` in the thousands and beyond, as
+//! [`BigUint`]s, plus count tables in any [`RankInt`] type.
+//!
+//! ## Algorithm Description
+//!
+//! **Euler's pentagonal number theorem**: `∏(1 − xⁿ) = Σ (−1)ᵏ x^{k(3k−1)/2}`
+//!   over all integers `k`, so
+//!   `p(n) = Σ_{k≥1} (−1)^{k+1} [p(n − k(3k−1)/2) + p(n − k(3k+1)/2)]`.
+//!   Only `O(√n)` earlier values enter each step.
+//! **Distinct parts**: `∏(1 + xⁿ) = P(x) · ∏(1 − x²ⁿ)`, so
+//!   `q(n) = p(n) + Σ_{k≥1} (−1)ᵏ [p(n − k(3k−1)) + p(n − k(3k+1))]`.
+//! **Bounded tables**: `T[i][j] = T[i][j−1] + T[i−j][j]` counts partitions of
+//!   `i` into parts of size at most `j`, i.e. into at most `j` parts.
+//!
+//! ## Complexity
+//!
+//! - `p(0..=n)`, `q(0..=n)`: O(n^1.5) big-integer additions
+//! - Bounded table up to `(n, k)`: O(n · k) additions, O(n) space for a
+//!   single entry
+
+use crate::arith::biguint::BigUint;
+use crate::arith::rank_int::{RankError, RankInt};
+
+/// The partition numbers `p(0), p(1), …, p(n)`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::count::partition_numbers;
+/// let p = partition_numbers(7);
+/// let p: Vec<u64> = p.iter().map(|x| x.to_u64().unwrap()).collect();
+/// assert_eq!(p, vec![1, 1, 2, 3, 5, 7, 11, 15]);
+/// ```
+pub fn partition_numbers(n: usize) -> Vec<BigUint> {
+    let mut p = Vec::with_capacity(n + 1);
+    p.push(BigUint::one());
+    for m in 1..=n {
+        let (mut plus, mut minus) = (BigUint::zero(), BigUint::zero());
+        for (k, g) in pentagonal_pairs(1).take_while(|&(_, g)| g <= m) {
+            let sign = if k % 2 == 1 { &mut plus } else { &mut minus };
+            *sign = &*sign + &p[m - g];
+            let g2 = g + k;
+            if g2 <= m {
+                *sign = &*sign + &p[m - g2];
+            }
+        }
+        p.push(plus - minus);
+    }
+    p
+}
+
+/// The partition number `p(n)`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::count::partition_number;
+/// assert_eq!(partition_number(1000).to_string(), "24061467864032622473692149727991");
+/// ```
+pub fn partition_number(n: usize) -> BigUint {
+    partition_numbers(n).swap_remove(n)
+}
+
+/// The numbers `q(0), q(1), …, q(n)` of partitions into distinct parts (which
+/// equal the numbers of partitions into odd parts).
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::count::distinct_partition_numbers;
+/// let q = distinct_partition_numbers(8);
+/// let q: Vec<u64> = q.iter().map(|x| x.to_u64().unwrap()).collect();
+/// assert_eq!(q, vec![1, 1, 1, 2, 2, 3, 4, 5, 6]);
+/// ```
+pub fn distinct_partition_numbers(n: usize) -> Vec<BigUint> {
+    let p = partition_numbers(n);
+    (0..=n)
+        .map(|m| {
+            let (mut plus, mut minus) = (p[m].clone(), BigUint::zero());
+            // terms of ∏(1 − x²ⁿ) sit at twice the pentagonal numbers
+            for (k, g) in pentagonal_pairs(1).take_while(|&(_, g)| 2 * g <= m) {
+                let sign = if k % 2 == 0 { &mut plus } else { &mut minus };
+                *sign = &*sign + &p[m - 2 * g];
+                let g2 = 2 * (g + k);
+                if g2 <= m {
+                    *sign = &*sign + &p[m - g2];
+                }
+            }
+            plus - minus
+        })
+        .collect()
+}
+
+/// Table `T[i][j]` of the number of partitions of `i` into at most `j` parts
+/// (equivalently, into parts of size at most `j`), for `i ≤ n` and `j ≤ k`.
+///
+/// # Returns
+///
+/// `Err(RankError::Overflow)` if an entry does not fit in `R`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::count::bounded_partition_table;
+/// let t: Vec<Vec<u64>> = bounded_partition_table(6, 3).unwrap();
+/// assert_eq!(t[6], vec![0, 1, 4, 7]);
+/// assert!(bounded_partition_table::<u64>(500, 500).is_err());
+/// ```
+pub fn bounded_partition_table<R: RankInt>(n: usize, k: usize) -> Result<Vec<Vec<R>>, RankError> {
+    checked_bounded_partition_table(n, k)
+        .into_iter()
+        .map(|row| row.into_iter().collect::<Option<Vec<R>>>())
+        .collect::<Option<Vec<_>>>()
+        .ok_or(RankError::Overflow)
+}
+
+/// The table of [`bounded_partition_table`], with `None` for each entry that
+/// does not fit in `R`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::count::checked_bounded_partition_table;
+/// let t = checked_bounded_partition_table::<u64>(500, 500);
+/// assert_eq!(t[500][2], Some(251));
+/// assert_eq!(t[500][500], None);
+/// ```
+pub fn checked_bounded_partition_table<R: RankInt>(n: usize, k: usize) -> Vec<Vec<Option<R>>> {
+    let mut col = first_column(n);
+    let mut t: Vec<Vec<Option<R>>> = col.iter().map(|x| vec![x.clone()]).collect();
+    for j in 1..=k {
+        add_part_size(&mut col, j);
+        for (row, x) in t.iter_mut().zip(&col) {
+            row.push(x.clone());
+        }
+    }
+    t
+}
+
+/// The entry `T[n][k]` of [`bounded_partition_table`]: the number of
+/// partitions of `n` into parts of size at most `k`, using O(n) space.
+///
+/// # Returns
+///
+/// `Err(RankError::Overflow)` if the count does not fit in `R`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::count::bounded_partition_count;
+/// assert_eq!(bounded_partition_count::<u64>(6, 2), Ok(4));
+/// ```
+pub fn bounded_partition_count<R: RankInt>(n: usize, k: usize) -> Result<R, RankError> {
+    let mut col = first_column(n);
+    for j in 1..=k.min(n) {
+        add_part_size(&mut col, j);
+    }
+    col.swap_remove(n).ok_or(RankError::Overflow)
+}
+
+/// Column `T[·][0]`: only the empty partition of 0.
+fn first_column<R: RankInt>(n: usize) -> Vec<Option<R>> {
+    let mut col = vec![Some(R::zero()); n + 1];
+    col[0] = R::from_usize(1);
+    col
+}
+
+/// Turn column `T[·][j−1]` into `T[·][j]` in place, by
+/// `T[i][j] = T[i][j−1] + T[i−j][j]`. An entry that overflows becomes `None`,
+/// and so does every later entry built from it.
+fn add_part_size<R: RankInt>(col: &mut [Option<R>], j: usize) {
+    for i in j..col.len() {
+        col[i] = match (&col[i], &col[i - j]) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+    }
+}
+
+/// Pairs `(k, k(3k−1)/2)` for `k = start, start+1, …`.
+fn pentagonal_pairs(start: usize) -> impl Iterator<Item = (usize, usize)> {
+    (start..).map(|k| (k, k * (3 * k - 1) / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partitions::rank::{checked_partition_count_bounded, partition_count_bounded_big};
+    use crate::partitions::restricted::PartitionConstraints;
+
+    #[test]
+    fn test_pentagonal_matches_dp() {
+        let p = partition_numbers(300);
+        for (n, x) in p.iter().enumerate().step_by(7) {
+            assert_eq!(*x, partition_count_bounded_big(n, n));
+        }
+        let q = distinct_partition_numbers(40);
+        for (n, x) in q.iter().enumerate() {
+            let expected = PartitionConstraints::new().distinct().count(n);
+            assert_eq!(x.to_u64(), expected.map(|c| c as u64));
+        }
+    }
+
+    #[test]
+    fn test_bounded_table_matches_counts() {
+        let t: Vec<Vec<usize>> = bounded_partition_table(30, 12).unwrap();
+        for (i, row) in t.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                assert_eq!(Some(x), checked_partition_count_bounded(i, j));
+            }
+        }
+        let big: Vec<Vec<BigUint>> = bounded_partition_table(450, 450).unwrap();
+        assert_eq!(big[450][450], partition_number(450));
+    }
+}
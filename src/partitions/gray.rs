@@ -0,0 +1,1106 @@
+//! # Chapter 6: Integer Partitions — Gray Code (Minimal-Change Order)
+//!
+//! List every partition of `n` so that successive partitions differ by moving
+//! a single unit from one part to another: one part shrinks by 1 and another
+//! grows by 1, where a part may vanish (1 → 0) or appear (0 → 1). For example
+//! `3 1 1 → 2 2 1` moves a unit from the 3 to a 1.
+//!
+//! Savage showed that such a listing exists for every `n`. The walk below is
+//! this crate's own construction, not her `L(n, k)` recursion, so its order
+//! need not match hers. The proof that it works for every `n` follows the
+//! description.
+//!
+//! ## Algorithm Description
+//!
+//! Write `E(z, i)` for the partition of `z` into parts `i` and one smaller
+//! remainder, and `S(z, i)` for the partitions of `z` with no part above `i`
+//! other than `1^z` and `2 1^(z−2)`. The walk is `1ⁿ`, `2 1^(n−2)`, then a walk
+//! `A(n, n)` of `S(n, n)` from `3 1^(n−3)` to `(n)`; for `n = 4` it is `B(4)`,
+//! which starts at `2 2`.
+//!
+//! Sort `S(z, i)` into *columns* by largest part. Column 2 holds
+//! `b₂ = 2 2 1^(z−4)` and the *chain* `2^k 1^(z−2k)`, `k ≥ 3`. Column `l ≥ 3`
+//! holds the rails `a_l = l 1^(z−l)` and `b_l = l 2 1^(z−l−2)` and the *rest*
+//! `R_l`, the partitions `l ρ` with `ρ` in `S(w, l)`, `w = z − l`. A walk of
+//! `S(w, l)` is a walk of `R_l` with the same moves. Rests are entered and left
+//! at the ports `E = l · E(w, l)`, `M = l · E(w, l − 1)`, `c = l 3 1^(w−3)` and
+//! `d = l 2 2 1^(w−4)`. The walks are built from these pieces:
+//!
+//! | piece     | walks     | from          | to                            |
+//! |-----------|-----------|---------------|-------------------------------|
+//! | `A(z, i)` | `S(z, i)` | `3 1^(z−3)`   | `E(z, i)`                     |
+//! | `V(z, i)` | `S(z, i)` | `E(z, i − 1)` | `E(z, i)`                     |
+//! | `T(z, j)` | `S(z, j)` | `E(z, j)`     | `b_j`                         |
+//! | `Q(z, j)` | `S(z, j)` | `c_j`         | `b_j`                         |
+//! | `K(z, j)` | `S(z, j)` | `3 1^(z−3)`   | `b_j` if `2j < z`, else `a_j` |
+//! | `W(m, k)` | `S(m, k)` | `E(m, k − 1)` | `3 1^(m−3)`                   |
+//! | `B(z)`    | `S(z, z)` | `2 2 1^(z−4)` | `(z)`                         |
+//!
+//! so a rest is walked from `c` to `E` by `A(w, l)`, from `d` to `E` by
+//! `B(w)`, from `M` to `E` by `V(w, l)` and from `M` to `c` by `W(w, l)`, or
+//! backwards. With `u = ⌈z/2⌉`, column `l` is *low* if `2l < z` and *high*
+//! otherwise; a high rest is `l · S(w, w)`, with `E = l w`. For `z ≥ 9`:
+//!
+//! 1. Low pieces, `2i < z` (`T(z, 2)` is the chain downwards and `b₂`):
+//!    - `A(z, i)`: `a₃ … a_i`, `b_i … b₂`, the chain upwards, then
+//!      `R₃ … R_i`, each from `M` to `E`.
+//!    - `T(z, j)`: `R_j … R₃`, each from `E` to `M`, the chain downwards,
+//!      `b₂`, then `a₃, b₃, …, a_j, b_j`.
+//!    - `K(z, j)`: `a₃ … a_j`, `b_{j−1} … b₂`, the chain upwards,
+//!      `R₃ … R_{j−1}` from `M` to `E`, `R_j` from `M` to `c`, then `b_j`.
+//!    - `Q(z, j)`: `R_j` from `c` to `M`, then as `T(z, j)`.
+//! 2. `A(z, i)` and `K(z, j)` with `2i ≥ z` walk a prefix, then the high
+//!    columns `u` and up. A column is walked up as `a_l, b_l`, then `R_l` from
+//!    `c` (`d` if `w = 4`) to `E`, or down in reverse; each starts next to
+//!    where the last one stopped, `a_l` after `a_{l−1}` or `E_l` after
+//!    `E_{l−1}`. The prefix is `K(z, u − 1)`, ending at `b_{u−1}`, or for odd
+//!    `z` `A(z, u − 1)`, ending next to `E_u = u (u − 1)`: whichever makes the
+//!    last column stop at `E(z, i)` for `A` or `a_j` for `K`. For even
+//!    `z = 2h` the prefix is `K`, and if the count of columns does not suit
+//!    it, columns `h` and `h + 1` are walked together first, as `a_h`,
+//!    `a_{h+1}`, `b_{h+1}`, `b_h`, then `P` backwards. `P` walks both rests
+//!    from `E_{h+1}` to `c_h`: `(h+1) (h−1)`, `h h`, `h (h−1) 1`, then
+//!    `(h + 1) · V(h − 1, h − 2)` backwards and `h · W(h, h − 2)`.
+//! 3. `T(z, j)` with `2j ≥ z` walks the high rests `R_j … R_u`, each from the
+//!    port the last one left: `R_l` is the single `l 3` if `w = 3`, goes from
+//!    `E` to `d` if `w = 4`, from `d` to `E` or `E` to `c` if `w = 5`, and
+//!    otherwise from `E` to `c` or `c` to `E` in turn. `h h` has no neighbour
+//!    in column `h − 1`, so for even `z` the walk must leave `R_h` at `c`;
+//!    where it would not, it reaches `R_{h+1}` at `E` and `P` walks both. Then
+//!    come `T(z, u − 1)` if the walk left at `E`, or `Q(z, u − 1)` if at `c`
+//!    (`d` for `z = 9`), and `a_u, b_u, …, a_j, b_j`.
+//! 4. `V(z, i)` is `T(z, i − 1)`, `a_i`, `b_i`, then `R_i` from `c` (`d` if
+//!    `w = 4`) to `E`; `V(z, z − 1)` is `(z−2) 2`, `T(z, z − 3)`, `(z−2) 1 1`,
+//!    `(z−1) 1`.
+//! 5. `W(m, m − 1)` is `(m−2) 2`, `(m−1) 1`, `(m−2) 1 1`, then `K(m, m − 3)`
+//!    backwards; `W(m, m − 2)` is `(m−3) 3`, `(m−2) 2`, `(m−2) 1 1`,
+//!    `(m−3) 2 1`, `(m−3) 1 1 1`, then `K(m, m − 4)` backwards.
+//!
+//! `A`, `V`, `W` and `B` with `z ≤ 8`, and `V(10, 4)`, are *base walks*
+//! instead, listed move by move in a table. Step 4 would walk `R₄` of
+//! `V(10, 4)` by `A(6, 4)`, and `S(6, 4)` has no walk from `3 1 1 1` to
+//! `4 2`. For the same reason `W(5, 4)` ends at `2 2 1` and `W(6, 5)` starts
+//! at `3 3`: `Q(9, 4)` then starts at `d₄`, where step 3 leaves `R₅`, and
+//! `5 3 3` follows the `E` port `4 4 3` of `R₄` in `K(11, 5)`.
+//!
+//! ## Why It Works
+//!
+//! By induction on `z`, then `i`. Every piece a step names has a smaller `z`
+//! (a rest, or the pieces inside `P`) or the same `z` and a smaller `i`, and
+//! lies where a step or the table applies: `T` and `V` for any `i`, `A`
+//! except `A(4, i)` and `A(6, 4)`, which only `V(7, 3)` and `V(10, 4)` would
+//! ask for, `K` and `Q` with `j = u − 1` or from `W`, `W(m, k)` with
+//! `k ≥ m − 2`, and `B(w)` for `w = 4, 5` only. Each step lists its set once,
+//! since the columns split `S(z, i)`, and `P` covers the rests of columns
+//! `h + 1` and `h`: the partitions of `h − 1` and `h` with a part above
+//! `h − 2` are `(h − 1)`, `(h)` and `(h − 1) 1`. Consecutive partitions differ
+//! by a unit move:
+//!
+//! - On the rails `a_l ~ b_l`, `a_l ~ a_{l+1}`, `b_l ~ b_{l+1}`, and `b_l`
+//!   is next to `c_l` and `d_l`. `b₂ ~ a₃`, and the chain runs from
+//!   `2³ 1^(z−6)`, next to `b₂`, to `2^⌊z/2⌋ 1^(z mod 2)`, next to `M` of `R₃`.
+//! - `E` of `R_l` is `E(z, l)`, next to `M` of `R_{l+1}`,
+//!   `(l + 1) · E(z − l − 1, l)`: a unit moves from the remainder, or from
+//!   an `l` when there is none, to an `l`.
+//! - In the high columns `l w ~ (l + 1) (w − 1)`, and likewise
+//!   `c_l ~ c_{l+1}` and `d_l ~ d_{l+1}`.
+//! - The nodes of steps 2, 4 and 5 meet the pieces around them as written.
+//!
+//! The base walks are the base of the induction. Each is a fixed list of at
+//! most 20 moves over a set of at most 21 partitions, which can be checked by
+//! hand; `test_base_walks` replays them all.
+//!
+//! The walk is produced lazily. A stack holds the pieces being walked, each
+//! as a handful of blocks (a rail, the chain, a row of rests or columns) read
+//! from either end, and the move between two segments is found from their
+//! end partitions. The base walks are played from their table. A count of
+//! the parts of each size or larger finds the part to shrink or grow in
+//! constant time.
+//!
+//! ## Complexity
+//!
+//! - Time: amortized O(1) per move; O(n²) once in [`GrayPartitions::new`] to
+//!   count `p(n)` for `size_hint`
+//! - Space: O(n)
+//!
+//! ## References
+//!
+//! - C. D. Savage, "Gray code sequences of partitions", J. Algorithms 10 (1989),
+//!   for the existence result; the walk here is not taken from it.
+//! - Knuth TAOCP Vol. 4A, 7.2.1.4, exercise 18.
+
+use std::cmp::Ordering;
+
+use crate::partitions::rank::checked_partition_count;
+
+/// Generate all partitions of `n` in minimal-change (Gray code) order.
+///
+/// Starts at `1ⁿ` and ends at `[n]`; each partition is non-increasing.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::gray::{gray_partitions, is_minimal_change_sequence};
+/// let parts = gray_partitions(5);
+/// assert_eq!(parts.len(), 7);
+/// assert_eq!(parts[0], vec![1, 1, 1, 1, 1]);
+/// assert_eq!(parts[6], vec![5]);
+/// assert!(is_minimal_change_sequence(&parts));
+/// ```
+pub fn gray_partitions(n: usize) -> Vec<Vec<usize>> {
+    GrayPartitions::new(n).collect()
+}
+
+/// The sequence of [`gray_partitions`], generated one unit move at a time.
+///
+/// Only the current partition and O(n) bookkeeping are kept, and each step
+/// edits the partition in place in amortized constant time.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::gray::GrayPartitions;
+/// let mut it = GrayPartitions::new(4);
+/// assert_eq!(it.current(), &[1, 1, 1, 1]);
+/// assert_eq!(it.next_move(), Some((1, 1))); // a 1 moves onto another 1
+/// assert_eq!(it.current(), &[2, 1, 1]);
+/// let rest: Vec<Vec<usize>> = it.collect();
+/// assert_eq!(rest, vec![vec![2, 2], vec![3, 1], vec![4]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrayPartitions {
+    part: Vec<usize>,
+    /// `ge[v]` is the number of parts of size at least `v`.
+    ge: Vec<usize>,
+    /// The pieces being walked, innermost last.
+    stack: Vec<Frame>,
+    /// Moves left, if `p(n)` fits in `usize`.
+    left: Option<usize>,
+    started: bool,
+}
+
+impl GrayPartitions {
+    /// Start at `1ⁿ`.
+    pub fn new(n: usize) -> Self {
+        let mut ge = vec![0; n + 2];
+        ge[1] = n;
+        GrayPartitions {
+            part: vec![1; n],
+            ge,
+            stack: vec![Frame::laid(n, plan(n), false)],
+            left: checked_partition_count(n).map(|p| p - 1),
+            started: false,
+        }
+    }
+
+    /// The current partition.
+    pub fn current(&self) -> &[usize] {
+        &self.part
+    }
+
+    /// Advance by one unit move.
+    ///
+    /// # Returns
+    ///
+    /// `Some((from, to))` if a unit left a part of size `from` and joined a
+    /// part of size `to` (`0` for a new part), or `None` once every partition
+    /// has been visited. The starting partition counts as visited.
+    pub fn next_move(&mut self) -> Option<(usize, usize)> {
+        self.started = true;
+        let (from, to) = self.step()?;
+        // Shrink the last part of size `from` and grow the first of size `to`,
+        // which keeps the parts in order.
+        let i = self.ge[from] - 1;
+        self.part[i] -= 1;
+        self.ge[from] -= 1;
+        if self.part[i] == 0 {
+            self.part.pop();
+        }
+        if to == 0 {
+            self.part.push(1);
+        } else {
+            self.part[self.ge[to + 1]] += 1;
+        }
+        self.ge[to + 1] += 1;
+        if let Some(left) = &mut self.left {
+            *left -= 1;
+        }
+        Some((from, to))
+    }
+
+    /// Advance to the next partition and lend it as a slice.
+    ///
+    /// The streaming counterpart of [`Iterator::next`].
+    pub fn next_slice(&mut self) -> Option<&[usize]> {
+        if self.started {
+            self.next_move()?;
+        }
+        self.started = true;
+        Some(&self.part)
+    }
+
+    /// Consume the generator, yielding only the moves.
+    ///
+    /// Produces `p(n) - 1` moves: one per step after `1ⁿ`.
+    pub fn moves(mut self) -> impl Iterator<Item = (usize, usize)> {
+        std::iter::from_fn(move || self.next_move())
+    }
+
+    /// The next move: inside a base walk, or between two segments.
+    fn step(&mut self) -> Option<(usize, usize)> {
+        loop {
+            let (mv, seg) = match self.stack.last_mut()? {
+                Frame::Base { moves, next, rev } => {
+                    let len = (moves.len() + 1) / 3;
+                    if *next == len {
+                        self.stack.pop();
+                        continue;
+                    }
+                    let k = *next;
+                    *next += 1;
+                    if *rev {
+                        let (from, to) = base_move(moves, len - 1 - k);
+                        return Some((to + 1, from - 1));
+                    }
+                    return Some(base_move(moves, k));
+                }
+                Frame::Laid { z, blocks, rev, block, next, last } => {
+                    let Some(seg) = next_seg(*z, blocks, *rev, block, next) else {
+                        self.stack.pop();
+                        continue;
+                    };
+                    let (first, end) = seg.ends();
+                    let mv = match last.replace(end) {
+                        Some(prev) => Some(between(&prev, &first)?),
+                        None => None,
+                    };
+                    (mv, seg)
+                }
+            };
+            if let Seg::Piece(p) = seg {
+                self.enter(p);
+            }
+            if mv.is_some() {
+                return mv;
+            }
+        }
+    }
+
+    /// Push a frame walking `p`.
+    fn enter(&mut self, p: Piece) {
+        let frame = match p.kind.base(p.z, p.i) {
+            Some(moves) => Frame::Base { moves: moves.as_bytes(), next: 0, rev: p.rev },
+            None => Frame::laid(p.z, p.kind.blocks(p.z, p.i), p.rev),
+        };
+        self.stack.push(frame);
+    }
+}
+
+impl Iterator for GrayPartitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        self.next_slice().map(<[usize]>::to_vec)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.left {
+            Some(left) => {
+                let r = left + usize::from(!self.started);
+                (r, Some(r))
+            }
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// A piece being walked.
+#[derive(Debug, Clone)]
+enum Frame {
+    /// A base walk: its moves in [`BASE`], and how many are played.
+    Base { moves: &'static [u8], next: usize, rev: bool },
+    /// A piece of blocks, with the next segment counted from the far end when
+    /// reversed, and the last partition of the segment before it.
+    Laid { z: usize, blocks: Vec<Block>, rev: bool, block: usize, next: usize, last: Option<Shape> },
+}
+
+impl Frame {
+    fn laid(z: usize, blocks: Vec<Block>, rev: bool) -> Frame {
+        Frame::Laid { z, blocks, rev, block: 0, next: 0, last: None }
+    }
+}
+
+/// The segment after `block` and `next` in `blocks`, read backwards if
+/// `rev`, and the position moved past it.
+fn next_seg(
+    z: usize,
+    blocks: &[Block],
+    rev: bool,
+    block: &mut usize,
+    next: &mut usize,
+) -> Option<Seg> {
+    while *block < blocks.len() {
+        let b = blocks[if rev { blocks.len() - 1 - *block } else { *block }];
+        let len = b.len(z);
+        if *next == len {
+            *block += 1;
+            *next = 0;
+            continue;
+        }
+        let k = if rev { len - 1 - *next } else { *next };
+        *next += 1;
+        let seg = b.get(z, k);
+        return Some(if rev { seg.reversed() } else { seg });
+    }
+    None
+}
+
+/// The top of the walk: `1ⁿ`, `2 1^(n−2)`, then `A(n, n)`, or `B(4)`.
+fn plan(n: usize) -> Vec<Block> {
+    let mut blocks = vec![node(Shape::of(&[(1, n)]))];
+    if n >= 2 {
+        blocks.push(node(Shape::of(&[(2, 1), (1, n - 2)])));
+    }
+    if n == 4 {
+        blocks.push(piece(Piece::new(Kind::Lift, 4, 4)));
+    } else if n >= 3 {
+        blocks.push(piece(Piece::new(Kind::Rise, n, n)));
+    }
+    blocks
+}
+
+/// A partition as runs `(size, count)`, largest size first. The partitions
+/// the pieces name have at most four sizes; the spare room holds the
+/// candidates tried by [`between`].
+#[derive(Debug, Clone, Copy)]
+struct Shape {
+    runs: [(usize, usize); 6],
+    len: usize,
+}
+
+impl Shape {
+    /// The partition with `count` parts of each `size`; zero sizes or counts
+    /// are skipped.
+    fn of(runs: &[(usize, usize)]) -> Shape {
+        let mut s = Shape { runs: [(0, 0); 6], len: 0 };
+        for &(size, count) in runs {
+            s.grow(size, count);
+        }
+        s
+    }
+
+    fn runs(&self) -> &[(usize, usize)] {
+        &self.runs[..self.len]
+    }
+
+    /// Add `count` parts of `size`.
+    fn grow(&mut self, size: usize, count: usize) {
+        if size == 0 || count == 0 {
+            return;
+        }
+        let k = self.runs().iter().position(|r| r.0 <= size).unwrap_or(self.len);
+        if k < self.len && self.runs[k].0 == size {
+            self.runs[k].1 += count;
+            return;
+        }
+        self.runs.copy_within(k..self.len, k + 1);
+        self.runs[k] = (size, count);
+        self.len += 1;
+    }
+
+    /// Remove one part of `size`, if there is one.
+    fn shrink(&mut self, size: usize) -> bool {
+        let Some(k) = self.runs().iter().position(|r| r.0 == size) else {
+            return false;
+        };
+        self.runs[k].1 -= 1;
+        if self.runs[k].1 == 0 {
+            self.runs.copy_within(k + 1..self.len, k);
+            self.len -= 1;
+        }
+        true
+    }
+}
+
+/// The unit move `(from, to)` taking `x` to `y`, or `None` if there is none,
+/// which the construction rules out.
+fn between(x: &Shape, y: &Shape) -> Option<(usize, usize)> {
+    for &(from, _) in x.runs() {
+        for to in x.runs().iter().map(|r| r.0).chain([0]) {
+            let mut s = *x;
+            s.shrink(from);
+            s.grow(from - 1, 1);
+            if to > 0 && !s.shrink(to) {
+                continue;
+            }
+            s.grow(to + 1, 1);
+            if from - 1 != to && s.runs() == y.runs() {
+                return Some((from, to));
+            }
+        }
+    }
+    None
+}
+
+/// `E(z, i)`: parts `i` and one smaller remainder.
+fn top(z: usize, i: usize) -> Shape {
+    match i.min(z) {
+        0 => Shape::of(&[]),
+        i => Shape::of(&[(i, z / i), (z % i, 1)]),
+    }
+}
+
+/// The rail `a_l = l 1^(z−l)`, or `b_l = l 2 1^(z−l−2)` if `two`.
+fn rail(z: usize, l: usize, two: bool) -> Shape {
+    if two {
+        Shape::of(&[(l, 1), (2, 1), (1, z - l - 2)])
+    } else {
+        Shape::of(&[(l, 1), (1, z - l)])
+    }
+}
+
+/// The pieces of the walk, named in the module docs by the letter given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `A(z, i)`, from `3 1^(z−3)` to `E(z, i)`.
+    Rise,
+    /// `V(z, i)`, from `E(z, i − 1)` to `E(z, i)`.
+    Step,
+    /// `T(z, i)`, from `E(z, i)` to `b_i`.
+    Back,
+    /// `Q(z, i)`, from `R_i`'s end of `W(z − i, i)` to `b_i`.
+    Turn,
+    /// `K(z, i)`, from `3 1^(z−3)` to `b_i`, or `a_i` if `2i ≥ z`.
+    Climb,
+    /// `W(z, i)`, from `E(z, i − 1)` to `3 1^(z−3)`.
+    Hook,
+    /// `B(z)`, from `2 2 1^(z−4)` to `(z)`.
+    Lift,
+    /// High column `i` walked up, from `a_i` to `E(z, i)`.
+    Column,
+    /// `P` for `z = 2i`, from `(i + 1) (i − 1)` to `c_i`.
+    Pair,
+    /// Columns `i` and `i + 1` for `z = 2i`, from `a_i` to `(i + 1) (i − 1)`.
+    Fold,
+}
+
+impl Kind {
+    /// The moves of the piece if it is one of the base walks in [`BASE`].
+    fn base(self, z: usize, i: usize) -> Option<&'static str> {
+        if z > 10 {
+            return None;
+        }
+        BASE.iter().find(|b| (b.0, b.1, b.2) == (self, z, i)).map(|b| b.3)
+    }
+
+    /// The first and last partitions of the piece.
+    fn ends(self, z: usize, i: usize) -> (Shape, Shape) {
+        let low = || rail(z, 3, false);
+        match self {
+            Kind::Rise => (low(), top(z, i)),
+            Kind::Step => (top(z, i - 1), top(z, i)),
+            Kind::Back => (top(z, i), rail(z, i, true)),
+            Kind::Turn => (rest(Kind::Hook, z, i).ends().1, rail(z, i, true)),
+            Kind::Climb => (low(), rail(z, i, 2 * i < z)),
+            Kind::Hook => (
+                if (z, i) == (6, 5) { Shape::of(&[(3, 2)]) } else { top(z, i - 1) },
+                if (z, i) == (5, 4) { Shape::of(&[(2, 2), (1, 1)]) } else { low() },
+            ),
+            Kind::Lift => (Shape::of(&[(2, 2), (1, z - 4)]), top(z, z)),
+            Kind::Column => (rail(z, i, false), top(z, i)),
+            Kind::Pair => {
+                (Shape::of(&[(i + 1, 1), (i - 1, 1)]), Shape::of(&[(i, 1), (3, 1), (1, i - 3)]))
+            }
+            Kind::Fold => (rail(z, i, false), Shape::of(&[(i + 1, 1), (i - 1, 1)])),
+        }
+    }
+
+    /// The blocks of a piece that is not a base walk, as in the module docs.
+    fn blocks(self, z: usize, i: usize) -> Vec<Block> {
+        use Block::{Chain, Rail, Rests, Zig};
+        let u = z.div_ceil(2);
+        let b2 = || node(rail(z, 2, true));
+        match self {
+            Kind::Rise if 2 * i < z => vec![
+                Rail { two: false, lo: 3, hi: i, down: false },
+                Rail { two: true, lo: 2, hi: i, down: true },
+                Chain(false),
+                Rests { lo: 3, hi: i, down: false },
+            ],
+            Kind::Rise => high(z, i, true),
+            Kind::Climb if 2 * i < z => vec![
+                Rail { two: false, lo: 3, hi: i, down: false },
+                Rail { two: true, lo: 2, hi: i - 1, down: true },
+                Chain(false),
+                Rests { lo: 3, hi: i - 1, down: false },
+                piece(rest(Kind::Hook, z, i)),
+                node(rail(z, i, true)),
+            ],
+            Kind::Climb => high(z, i, false),
+            Kind::Step if i + 1 == z => vec![
+                node(Shape::of(&[(z - 2, 1), (2, 1)])),
+                piece(Piece::new(Kind::Back, z, z - 3)),
+                node(Shape::of(&[(z - 2, 1), (1, 2)])),
+                node(Shape::of(&[(z - 1, 1), (1, 1)])),
+            ],
+            Kind::Step => {
+                let mut blocks = vec![
+                    piece(Piece::new(Kind::Back, z, i - 1)),
+                    node(rail(z, i, false)),
+                    node(rail(z, i, true)),
+                ];
+                if z - i >= 3 {
+                    blocks.push(piece(rest(upper(z - i), z, i)));
+                }
+                blocks
+            }
+            Kind::Back if i == 2 => vec![Chain(true), b2()],
+            Kind::Back if 2 * i < z => {
+                vec![Rests { lo: 3, hi: i, down: true }, Chain(true), b2(), Zig(3, i)]
+            }
+            Kind::Back => pass(z, i),
+            Kind::Turn => vec![
+                piece(rest(Kind::Hook, z, i).reversed()),
+                Rests { lo: 3, hi: i - 1, down: true },
+                Chain(true),
+                b2(),
+                Zig(3, i),
+            ],
+            Kind::Hook if i + 1 == z => vec![
+                node(Shape::of(&[(z - 2, 1), (2, 1)])),
+                node(Shape::of(&[(z - 1, 1), (1, 1)])),
+                node(Shape::of(&[(z - 2, 1), (1, 2)])),
+                piece(Piece::new(Kind::Climb, z, z - 3).reversed()),
+            ],
+            Kind::Hook => vec![
+                node(Shape::of(&[(z - 3, 1), (3, 1)])),
+                node(Shape::of(&[(z - 2, 1), (2, 1)])),
+                node(Shape::of(&[(z - 2, 1), (1, 2)])),
+                node(Shape::of(&[(z - 3, 1), (2, 1), (1, 1)])),
+                node(Shape::of(&[(z - 3, 1), (1, 3)])),
+                piece(Piece::new(Kind::Climb, z, z - 4).reversed()),
+            ],
+            // `B(z)` is only asked for with `z = 4, 5`, both base walks.
+            Kind::Lift => Vec::new(),
+            Kind::Column => {
+                let w = z - i;
+                let mut blocks = vec![node(rail(z, i, false))];
+                if w >= 2 {
+                    blocks.push(node(rail(z, i, true)));
+                }
+                if w >= 3 {
+                    blocks.push(piece(rest(upper(w), z, i)));
+                }
+                blocks
+            }
+            Kind::Pair => vec![
+                node(Shape::of(&[(i + 1, 1), (i - 1, 1)])),
+                node(Shape::of(&[(i, 2)])),
+                node(Shape::of(&[(i, 1), (i - 1, 1), (1, 1)])),
+                piece(Piece::new(Kind::Step, i - 1, i - 2).under(i + 1).reversed()),
+                piece(Piece::new(Kind::Hook, i, i - 2).under(i)),
+            ],
+            Kind::Fold => vec![
+                node(rail(z, i, false)),
+                node(rail(z, i + 1, false)),
+                node(rail(z, i + 1, true)),
+                node(rail(z, i, true)),
+                piece(Piece::new(Kind::Pair, z, u).reversed()),
+            ],
+        }
+    }
+}
+
+/// The piece walking `R_l` of `z`: `kind` on `w = z − l`, under a part `l`.
+fn rest(kind: Kind, z: usize, l: usize) -> Piece {
+    Piece::new(kind, z - l, l).under(l)
+}
+
+/// The piece walking a rest of size `w` up from `c`, or from `d` if `w = 4`.
+fn upper(w: usize) -> Kind {
+    if w == 4 { Kind::Lift } else { Kind::Rise }
+}
+
+/// Step 2: the high columns `u..=i` after their prefix, stopping at `E(z, i)`
+/// if `to_top`, else at `a_i`.
+fn high(z: usize, i: usize, to_top: bool) -> Vec<Block> {
+    let u = z.div_ceil(2);
+    let even = (i + 1 - u).is_multiple_of(2);
+    let climb = piece(Piece::new(Kind::Climb, z, u - 1));
+    if !z.is_multiple_of(2) {
+        let prefix = if even == to_top { piece(Piece::new(Kind::Rise, z, u - 1)) } else { climb };
+        vec![prefix, Block::Columns { lo: u, hi: i, down: even == to_top }]
+    } else if even == to_top {
+        vec![
+            climb,
+            piece(Piece::new(Kind::Fold, z, u)),
+            Block::Columns { lo: u + 2, hi: i, down: true },
+        ]
+    } else {
+        vec![climb, Block::Columns { lo: u, hi: i, down: false }]
+    }
+}
+
+/// The port of a high rest that a walk of [`pass`] stops at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Port {
+    /// `E = l w`.
+    Top,
+    /// `c = l 3 1^(w−3)`.
+    Three,
+    /// `d = l 2 2 1^(w−4)`.
+    Twos,
+}
+
+/// Step 3: `T(z, j)` with `2j ≥ z`.
+fn pass(z: usize, j: usize) -> Vec<Block> {
+    let (u, h) = (z.div_ceil(2), z / 2);
+    let pair = || piece(Piece::new(Kind::Pair, z, h));
+    let mut blocks = Vec::new();
+    let (mut l, mut port) = (j, Port::Top);
+    // Rests of at most 5, whose ports do not simply alternate.
+    while l >= u && z - l <= 5 {
+        if z.is_multiple_of(2) && l == h + 1 && port == Port::Top {
+            blocks.push(pair());
+            port = Port::Three;
+            l = h - 1;
+            break;
+        }
+        let (b, next) = match (z - l, port) {
+            (3, _) => (node(Shape::of(&[(l, 1), (3, 1)])), Port::Top),
+            (4, _) => (piece(rest(Kind::Lift, z, l).reversed()), Port::Twos),
+            (_, Port::Twos) => (piece(rest(Kind::Lift, z, l)), Port::Top),
+            _ => (piece(rest(Kind::Rise, z, l).reversed()), Port::Three),
+        };
+        blocks.push(b);
+        port = next;
+        l -= 1;
+    }
+    if l >= u {
+        let from_top = port == Port::Top;
+        let ends_top = from_top == (l + 1 - u).is_multiple_of(2);
+        if z.is_multiple_of(2) && ends_top {
+            blocks.push(Block::Swing { lo: h + 2, hi: l, from_top });
+            blocks.push(pair());
+            port = Port::Three;
+        } else {
+            blocks.push(Block::Swing { lo: u, hi: l, from_top });
+            port = if ends_top { Port::Top } else { Port::Three };
+        }
+    }
+    let kind = if port == Port::Top { Kind::Back } else { Kind::Turn };
+    blocks.push(piece(Piece::new(kind, z, u - 1)));
+    blocks.push(Block::Zig(u, j));
+    blocks
+}
+
+/// A piece, with a part to put in front of each partition and a direction.
+#[derive(Debug, Clone, Copy)]
+struct Piece {
+    kind: Kind,
+    z: usize,
+    i: usize,
+    /// A part added to every partition, or 0.
+    lead: usize,
+    rev: bool,
+}
+
+impl Piece {
+    fn new(kind: Kind, z: usize, i: usize) -> Piece {
+        Piece { kind, z, i: i.min(z), lead: 0, rev: false }
+    }
+
+    /// The same walk with a part `lead` in every partition.
+    fn under(self, lead: usize) -> Piece {
+        Piece { lead, ..self }
+    }
+
+    fn reversed(self) -> Piece {
+        Piece { rev: !self.rev, ..self }
+    }
+
+    fn ends(&self) -> (Shape, Shape) {
+        let (mut first, mut last) = self.kind.ends(self.z, self.i);
+        first.grow(self.lead, 1);
+        last.grow(self.lead, 1);
+        if self.rev { (last, first) } else { (first, last) }
+    }
+}
+
+/// A stretch of a piece: one partition, or a piece walked in full.
+#[derive(Debug, Clone, Copy)]
+enum Seg {
+    Node(Shape),
+    Piece(Piece),
+}
+
+impl Seg {
+    fn ends(&self) -> (Shape, Shape) {
+        match self {
+            Seg::Node(s) => (*s, *s),
+            Seg::Piece(p) => p.ends(),
+        }
+    }
+
+    fn reversed(self) -> Seg {
+        match self {
+            Seg::Piece(p) => Seg::Piece(p.reversed()),
+            node => node,
+        }
+    }
+}
+
+fn node(s: Shape) -> Block {
+    Block::One(Seg::Node(s))
+}
+
+fn piece(p: Piece) -> Block {
+    Block::One(Seg::Piece(p))
+}
+
+/// A row of segments of a piece of `z`, read by index.
+#[derive(Debug, Clone, Copy)]
+enum Block {
+    One(Seg),
+    /// `a_l`, or `b_l` if `two`, for `l` in `lo..=hi`, downwards if `down`.
+    Rail {
+        two: bool,
+        lo: usize,
+        hi: usize,
+        down: bool,
+    },
+    /// `a_lo, b_lo, …, a_hi, b_hi`.
+    Zig(usize, usize),
+    /// The chain `2^k 1^(z−2k)`, `k ≥ 3`, downwards if set.
+    Chain(bool),
+    /// `R_lo … R_hi`, each from `M` to `E`, or if `down` `R_hi … R_lo` from
+    /// `E` to `M`.
+    Rests {
+        lo: usize,
+        hi: usize,
+        down: bool,
+    },
+    /// High columns `lo..=hi`, walked up and down in turn, starting down if
+    /// `down`.
+    Columns {
+        lo: usize,
+        hi: usize,
+        down: bool,
+    },
+    /// High rests `R_hi … R_lo`, from `E` to `c` and `c` to `E` in turn,
+    /// starting at `E` if `from_top`.
+    Swing {
+        lo: usize,
+        hi: usize,
+        from_top: bool,
+    },
+}
+
+impl Block {
+    fn len(&self, z: usize) -> usize {
+        match *self {
+            Block::One(_) => 1,
+            Block::Zig(lo, hi) => 2 * (hi + 1).saturating_sub(lo),
+            Block::Chain(_) => (z / 2).saturating_sub(2),
+            Block::Rail { lo, hi, .. }
+            | Block::Rests { lo, hi, .. }
+            | Block::Columns { lo, hi, .. }
+            | Block::Swing { lo, hi, .. } => (hi + 1).saturating_sub(lo),
+        }
+    }
+
+    /// Segment `k`, counting from the start.
+    fn get(&self, z: usize, k: usize) -> Seg {
+        match *self {
+            Block::One(seg) => seg,
+            Block::Rail { two, lo, hi, down } => {
+                Seg::Node(rail(z, if down { hi - k } else { lo + k }, two))
+            }
+            Block::Zig(lo, _) => Seg::Node(rail(z, lo + k / 2, k % 2 == 1)),
+            Block::Chain(down) => {
+                let twos = if down { z / 2 - k } else { 3 + k };
+                Seg::Node(Shape::of(&[(2, twos), (1, z - 2 * twos)]))
+            }
+            Block::Rests { lo, hi, down } => {
+                let p = rest(Kind::Step, z, if down { hi - k } else { lo + k });
+                Seg::Piece(if down { p.reversed() } else { p })
+            }
+            Block::Columns { lo, down, .. } => {
+                let p = Piece::new(Kind::Column, z, lo + k);
+                Seg::Piece(if down == k.is_multiple_of(2) { p.reversed() } else { p })
+            }
+            Block::Swing { hi, from_top, .. } => {
+                let p = rest(Kind::Rise, z, hi - k);
+                Seg::Piece(if from_top == k.is_multiple_of(2) { p.reversed() } else { p })
+            }
+        }
+    }
+}
+
+/// The base walks, each as `(kind, z, i, moves)`: the moves of the piece from
+/// its first partition, each written as the digits of its `from` and `to`.
+const BASE: &[(Kind, usize, usize, &str)] = &[
+    (Kind::Rise, 3, 3, ""),
+    (Kind::Step, 4, 3, "22"),
+    (Kind::Hook, 4, 3, "22"),
+    (Kind::Lift, 4, 4, "22 13"),
+    (Kind::Rise, 5, 3, "31 12"),
+    (Kind::Step, 5, 3, "22 11"),
+    (Kind::Hook, 5, 3, "12 20"),
+    (Kind::Rise, 5, 4, "31 12 23"),
+    (Kind::Step, 5, 4, "30 22 13"),
+    (Kind::Hook, 5, 4, "23 40 31"),
+    (Kind::Rise, 5, 5, "31 12 23 14"),
+    (Kind::Lift, 5, 5, "12 20 13 14"),
+    (Kind::Rise, 6, 3, "31 11 22 12"),
+    (Kind::Step, 6, 3, "20 22 11 12"),
+    (Kind::Step, 6, 4, "30 31 20 22 13 11"),
+    (Kind::Hook, 6, 4, "33 20 41 31 20 22"),
+    (Kind::Rise, 6, 5, "31 11 22 12 33 20 14"),
+    (Kind::Step, 6, 5, "42 30 31 20 22 13 14"),
+    (Kind::Hook, 6, 5, "33 24 50 41 31 20 22"),
+    (Kind::Rise, 6, 6, "31 11 22 12 33 20 14 15"),
+    (Kind::Rise, 7, 3, "31 11 12 20 12"),
+    (Kind::Step, 7, 3, "20 22 11 11 22"),
+    (Kind::Rise, 7, 4, "13 11 41 30 20 12 12 13"),
+    (Kind::Step, 7, 4, "31 30 20 22 13 41 13 12"),
+    (Kind::Rise, 7, 5, "31 11 12 22 13 30 40 23 14 11"),
+    (Kind::Step, 7, 5, "40 31 30 20 22 11 23 14 51 14"),
+    (Kind::Hook, 7, 5, "34 20 50 11 42 31 30 22 30 22"),
+    (Kind::Rise, 7, 6, "31 11 12 22 13 34 50 40 23 14 15"),
+    (Kind::Step, 7, 6, "52 40 31 30 20 22 11 13 20 14 15"),
+    (Kind::Hook, 7, 6, "25 60 50 11 12 40 31 30 22 30 22"),
+    (Kind::Rise, 7, 7, "31 11 12 22 13 34 50 40 23 14 15 16"),
+    (Kind::Rise, 8, 3, "31 12 31 11 22 22 11"),
+    (Kind::Step, 8, 3, "20 20 22 11 12 31 12"),
+    (Kind::Rise, 8, 4, "13 11 11 42 30 31 20 20 12 12 13 13"),
+    (Kind::Step, 8, 4, "33 20 20 40 31 12 31 11 22 22 13 13"),
+    (Kind::Rise, 8, 5, "31 11 11 22 12 33 24 20 50 41 13 42 13 13 44"),
+    (Kind::Step, 8, 5, "40 41 33 40 31 20 20 22 13 41 12 33 24 11 12"),
+    (Kind::Rise, 8, 6, "31 11 11 22 12 33 22 13 44 30 50 42 30 23 14 15 11"),
+    (Kind::Step, 8, 6, "53 40 41 33 24 50 42 31 31 20 20 12 20 13 14 15 11"),
+    (Kind::Hook, 8, 6, "53 40 41 33 24 15 20 60 50 11 42 31 31 20 22 30 22"),
+    (Kind::Rise, 8, 7, "31 11 11 22 12 33 22 13 44 35 60 50 42 30 23 14 15 16"),
+    (Kind::Step, 8, 7, "62 53 40 41 33 24 50 42 31 31 20 20 12 20 13 14 15 16"),
+    (Kind::Hook, 8, 7, "26 70 60 50 11 14 12 53 40 31 42 20 31 31 20 22 30 22"),
+    (Kind::Rise, 8, 8, "31 11 11 22 12 33 22 13 44 35 60 50 42 30 23 14 15 16 17"),
+    (Kind::Step, 10, 4, "13 30 31 42 20 33 41 31 20 20 20 22 13 41 11 23 42 13 13 11"),
+];
+
+/// Move `k` of a base walk, from its moves as written in [`BASE`].
+fn base_move(moves: &[u8], k: usize) -> (usize, usize) {
+    (usize::from(moves[3 * k] - b'0'), usize::from(moves[3 * k + 1] - b'0'))
+}
+
+/// Whether `b` arises from `a` by moving one unit from one part to another.
+///
+/// Parts are compared as multisets, so their order does not matter; zero
+/// parts are ignored.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::gray::is_minimal_change;
+/// assert!(is_minimal_change(&[3, 1, 1], &[2, 2, 1]));
+/// assert!(is_minimal_change(&[2, 1], &[1, 1, 1])); // a new part appears
+/// assert!(!is_minimal_change(&[3, 1], &[2, 2, 1]));
+/// assert!(!is_minimal_change(&[2, 2], &[2, 2]));
+/// ```
+pub fn is_minimal_change(a: &[usize], b: &[usize]) -> bool {
+    unit_move(&sorted_parts(a), &sorted_parts(b)).is_some()
+}
+
+/// Whether every consecutive pair in `seq` is a minimal change and no
+/// partition (as a multiset of parts) occurs twice.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::gray::is_minimal_change_sequence;
+/// assert!(is_minimal_change_sequence(&[vec![1, 1, 1], vec![2, 1], vec![3]]));
+/// assert!(!is_minimal_change_sequence(&[vec![1, 1, 1], vec![3], vec![2, 1]]));
+/// ```
+pub fn is_minimal_change_sequence<P: AsRef<[usize]>>(seq: &[P]) -> bool {
+    let mut seen: Vec<Vec<usize>> = seq.iter().map(|p| sorted_parts(p.as_ref())).collect();
+    if !seen.windows(2).all(|w| unit_move(&w[0], &w[1]).is_some()) {
+        return false;
+    }
+    seen.sort_unstable();
+    seen.windows(2).all(|w| w[0] != w[1])
+}
+
+/// The positive parts of `p` in non-increasing order.
+fn sorted_parts(p: &[usize]) -> Vec<usize> {
+    let mut v: Vec<usize> = p.iter().copied().filter(|&x| x > 0).collect();
+    v.sort_unstable_by(|x, y| y.cmp(x));
+    v
+}
+
+/// The unit move `(from, to)` taking `a` to `b`, both non-increasing; `to` is
+/// `0` when the unit starts a new part.
+fn unit_move(a: &[usize], b: &[usize]) -> Option<(usize, usize)> {
+    // Multiset differences a \ b and b \ a by merging the sorted parts.
+    let (mut lost, mut gained) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let ord = match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) => y.cmp(x),
+            (Some(_), None) => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+        match ord {
+            Ordering::Equal => {
+                i += 1;
+                j += 1;
+            }
+            Ordering::Less => {
+                lost.push(a[i]);
+                i += 1;
+            }
+            Ordering::Greater => {
+                gained.push(b[j]);
+                j += 1;
+            }
+        }
+        if lost.len() > 2 || gained.len() > 2 {
+            return None;
+        }
+    }
+    for (k, &from) in lost.iter().enumerate() {
+        let to = if lost.len() == 2 { lost[1 - k] } else { 0 };
+        if from - 1 == to {
+            continue;
+        }
+        let mut want: Vec<usize> = [from - 1, to + 1].into_iter().filter(|&x| x > 0).collect();
+        want.sort_unstable_by(|x, y| y.cmp(x));
+        if want == gained {
+            return Some((from, to));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partitions::count::checked_bounded_partition_table;
+    use crate::partitions::rank::checked_partition_count;
+
+    #[test]
+    fn test_gray_partitions_visit_each_once_by_unit_moves() {
+        for n in 0..=50 {
+            let seq = gray_partitions(n);
+            assert_eq!(Some(seq.len()), checked_partition_count(n), "n = {n}");
+            assert!(seq.iter().all(|p| p.iter().sum::<usize>() == n));
+            assert!(seq.iter().all(|p| p.windows(2).all(|w| w[0] >= w[1])));
+            assert!(is_minimal_change_sequence(&seq), "n = {n}");
+            assert_eq!(seq[0], vec![1; n]);
+            if n > 0 {
+                assert_eq!(seq[seq.len() - 1], vec![n]);
+            }
+        }
+    }
+
+    /// The range checked beyond the default test, by streaming each walk and
+    /// marking the rank of every partition in a bitset.
+    #[test]
+    #[ignore = "takes minutes in debug builds; run with `cargo test --release -- --ignored`"]
+    fn test_gray_partitions_verified_range() {
+        for n in 51..=85 {
+            let table = checked_bounded_partition_table::<usize>(n, n);
+            let total = table[n][n].unwrap();
+            let mut seen = vec![false; total];
+            let mut it = GrayPartitions::new(n);
+            let mut prev = it.current().to_vec();
+            let mut visited = 0;
+            loop {
+                let rank = rank_with(it.current(), n, &table);
+                assert!(!std::mem::replace(&mut seen[rank], true), "n = {n}");
+                visited += 1;
+                if it.next_move().is_none() {
+                    break;
+                }
+                assert!(unit_move(&prev, it.current()).is_some(), "n = {n}");
+                prev.clear();
+                prev.extend_from_slice(it.current());
+            }
+            assert_eq!(visited, total, "n = {n}");
+            assert_eq!(it.current(), &[n]);
+        }
+    }
+
+    /// Rank of `part` in [`rank_partition`](crate::partitions::rank::rank_partition)
+    /// order, reusing one table.
+    fn rank_with(part: &[usize], n: usize, p: &[Vec<Option<usize>>]) -> usize {
+        let (mut rank, mut rem, mut prev_max) = (0, n, n);
+        for &x in part {
+            for v in (x + 1)..=prev_max.min(rem) {
+                rank += p[rem - v][v].unwrap();
+            }
+            rem -= x;
+            prev_max = x;
+        }
+        rank
+    }
+
+    #[test]
+    fn test_gray_iterator_reports_its_moves() {
+        for n in 0..=20 {
+            let mut it = GrayPartitions::new(n);
+            assert_eq!(Some(it.size_hint().0), checked_partition_count(n));
+            let mut prev = it.current().to_vec();
+            while let Some(mv) = it.next_move() {
+                assert_eq!(unit_move(&prev, it.current()), Some(mv), "n = {n}");
+                prev = it.current().to_vec();
+            }
+            assert_eq!(it.size_hint(), (0, Some(0)));
+        }
+        let moves: Vec<(usize, usize)> = GrayPartitions::new(3).moves().collect();
+        assert_eq!(moves, vec![(1, 1), (1, 2)]);
+    }
+
+    /// The parts of `s`, largest first.
+    fn parts(s: &Shape) -> Vec<usize> {
+        s.runs().iter().flat_map(|&(size, count)| std::iter::repeat_n(size, count)).collect()
+    }
+
+    /// The base of the proof: each base walk visits its whole set once, by
+    /// unit moves, between the ends of its piece.
+    #[test]
+    fn test_base_walks() {
+        let table = checked_bounded_partition_table::<usize>(10, 10);
+        for &(kind, z, i, moves) in BASE {
+            assert_eq!(kind.base(z, i), Some(moves));
+            let (first, last) = kind.ends(z, i);
+            let mut walk = vec![parts(&first)];
+            for k in 0..(moves.len() + 1) / 3 {
+                let (from, to) = base_move(moves.as_bytes(), k);
+                let mut p = walk[walk.len() - 1].clone();
+                let at = p.iter().position(|&x| x == from).unwrap();
+                p.remove(at);
+                if to > 0 {
+                    let at = p.iter().position(|&x| x == to).unwrap();
+                    p.remove(at);
+                }
+                p.extend([from - 1, to + 1]);
+                walk.push(sorted_parts(&p));
+            }
+            let name = format!("{kind:?}({z}, {i})");
+            assert_eq!(walk[walk.len() - 1], parts(&last), "{name}");
+            assert!(is_minimal_change_sequence(&walk), "{name}");
+            // `S(z, i)` leaves out `1^z` and `2 1^(z−2)`.
+            assert!(walk.iter().all(|p| p[0] <= i && p.len() + 2 <= z), "{name}");
+            assert_eq!(walk.len() + 2, table[z][i].unwrap(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_minimal_change_checks() {
+        assert!(is_minimal_change(&[1, 3, 1], &[2, 1, 2]));
+        assert!(is_minimal_change(&[4], &[3, 1]));
+        assert!(!is_minimal_change(&[4], &[2, 2]));
+        assert!(!is_minimal_change(&[3, 1], &[3, 1]));
+        assert!(!is_minimal_change_sequence(&[vec![2], vec![1, 1], vec![2]]));
+        assert!(is_minimal_change_sequence::<Vec<usize>>(&[]));
+    }
+}
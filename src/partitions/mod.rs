@@ -1,2 +1,7 @@
+pub mod count;
 pub mod generate;
-pub mod rank;
\ No newline at end of file
+pub mod gray;
+pub mod partition;
+pub mod random;
+pub mod rank;
+pub mod restricted;
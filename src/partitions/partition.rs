@@ -0,0 +1,411 @@
+//! # Chapter 6: Integer Partitions — The Partition Type
+//!
+//! A validated integer partition (positive parts in non-increasing order) with
+//! the classical transforms on Ferrers diagrams.
+//!
+//! ## Algorithm Description
+//!
+//! - **Conjugate**: `λ'ⱼ = #{i : λᵢ > j}`, the diagram reflected in its diagonal.
+//! - **Durfee square**: the largest `d` with `λ_d ≥ d` (1-based).
+//! - **Frobenius coordinates**: `aᵢ = λᵢ − i − 1`, `bᵢ = λ'ᵢ − i − 1` for
+//!   `i < d` (0-based): arm and leg lengths of the diagonal cells.
+//! - **Dominance**: `λ ⊵ μ` iff `|λ| = |μ|` and every prefix sum of `λ` is at
+//!   least the matching prefix sum of `μ`.
+//! - **Glaisher**: an odd part `o` repeated `m = Σ 2ᵏ` times becomes the
+//!   distinct parts `o · 2ᵏ`; the inverse splits `o · 2ᵏ` into `2ᵏ` copies of `o`.
+//! - **Sylvester**: write the odd parts as `2αᵢ + 1` (`ℓ` parts) and let `α'`
+//!   be the conjugate of `α`. With `s₀ = ℓ`, `s₂ⱼ₋₁ = αⱼ − (j − 1)` and
+//!   `s₂ⱼ = α'ⱼ − j`, the distinct parts are the positive terms of
+//!   `hᵢ = sᵢ₋₁ + sᵢ` — the successive hooks of the symmetric diagram of the
+//!   odd parts. The `h` are strictly decreasing, and the last positive one
+//!   ends on `s_r = 0`. The inverse therefore reads `ℓ = h₁ − h₂ + h₃ − …`
+//!   off the telescoping sum, then `sᵢ = hᵢ − sᵢ₋₁` gives the rows `αⱼ` and
+//!   columns `α'ⱼ` of the hooks; the remaining rows are counted from the
+//!   columns.
+//!
+//! ## Complexity
+//!
+//! - Conjugate, Frobenius, dominance, Glaisher: O(n)
+//! - Sylvester and its inverse: O(n)
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+
+/// Errors produced when constructing or transforming a [`Partition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionError {
+    /// The parts are not positive and non-increasing.
+    NotAPartition,
+    /// The transform needs every part to be odd.
+    NotOddParts,
+    /// The transform needs every part to be distinct.
+    NotDistinctParts,
+    /// The arm and leg sequences are not valid Frobenius coordinates.
+    InvalidFrobenius,
+}
+
+impl fmt::Display for PartitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartitionError::NotAPartition => f.write_str("parts are not positive and non-increasing"),
+            PartitionError::NotOddParts => f.write_str("partition has an even part"),
+            PartitionError::NotDistinctParts => f.write_str("partition has a repeated part"),
+            PartitionError::InvalidFrobenius => f.write_str("invalid Frobenius coordinates"),
+        }
+    }
+}
+
+impl std::error::Error for PartitionError {}
+
+/// An integer partition: positive parts in non-increasing order.
+///
+/// Dereferences to `[usize]`, so it can be passed wherever a shape slice is
+/// expected, e.g. [`generate_syt`](crate::young::generate::generate_syt).
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::partition::Partition;
+/// use rusty_combinatorial::young::generate::generate_syt;
+/// let p = Partition::new(vec![3, 1]).unwrap();
+/// assert_eq!(p.conjugate().as_slice(), &[2, 1, 1]);
+/// let _tableaux = generate_syt(&p);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Partition(Vec<usize>);
+
+impl Partition {
+    /// Validate `parts` as positive and non-increasing.
+    pub fn new(parts: Vec<usize>) -> Result<Self, PartitionError> {
+        let valid = parts.iter().all(|&x| x > 0) && parts.windows(2).all(|w| w[0] >= w[1]);
+        if !valid {
+            return Err(PartitionError::NotAPartition);
+        }
+        Ok(Partition(parts))
+    }
+
+    /// The parts as a slice.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// The parts as an owned vector.
+    pub fn into_vec(self) -> Vec<usize> {
+        self.0
+    }
+
+    /// The number being partitioned, `|λ| = Σ λᵢ`.
+    pub fn size(&self) -> usize {
+        self.0.iter().sum()
+    }
+
+    /// The conjugate partition (rows and columns of the diagram swapped).
+    pub fn conjugate(&self) -> Partition {
+        let cols = self.0.first().copied().unwrap_or(0);
+        Partition((0..cols).map(|j| self.0.iter().take_while(|&&x| x > j).count()).collect())
+    }
+
+    /// Side length of the Durfee square, the largest square inside the diagram.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::partitions::partition::Partition;
+    /// assert_eq!(Partition::new(vec![4, 3, 3, 1]).unwrap().durfee_size(), 3);
+    /// ```
+    pub fn durfee_size(&self) -> usize {
+        self.0.iter().enumerate().take_while(|&(i, &x)| x > i).count()
+    }
+
+    /// Frobenius coordinates `(a, b)`: arm and leg lengths of the diagonal cells.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::partitions::partition::Partition;
+    /// let p = Partition::new(vec![4, 3, 3, 1]).unwrap();
+    /// assert_eq!(p.frobenius(), (vec![3, 1, 0], vec![3, 1, 0]));
+    /// ```
+    pub fn frobenius(&self) -> (Vec<usize>, Vec<usize>) {
+        let d = self.durfee_size();
+        let conj = self.conjugate();
+        let arms = (0..d).map(|i| self.0[i] - i - 1).collect();
+        let legs = (0..d).map(|i| conj.0[i] - i - 1).collect();
+        (arms, legs)
+    }
+
+    /// Rebuild a partition from its Frobenius coordinates.
+    ///
+    /// # Returns
+    ///
+    /// `Err(PartitionError::InvalidFrobenius)` unless `arms` and `legs` have the
+    /// same length and are both strictly decreasing.
+    pub fn from_frobenius(arms: &[usize], legs: &[usize]) -> Result<Self, PartitionError> {
+        let decreasing = |v: &[usize]| v.windows(2).all(|w| w[0] > w[1]);
+        if arms.len() != legs.len() || !decreasing(arms) || !decreasing(legs) {
+            return Err(PartitionError::InvalidFrobenius);
+        }
+        let d = arms.len();
+        let rows = legs.first().map_or(0, |&b| b + 1);
+        let parts = (0..rows)
+            .map(|i| match arms.get(i) {
+                Some(&a) => a + i + 1,
+                // below the Durfee square: count columns j < d reaching row i
+                None => (0..d).filter(|&j| legs[j] + j >= i).count(),
+            })
+            .collect();
+        Ok(Partition(parts))
+    }
+
+    /// `true` if `self` dominates `other`: same size and every prefix sum of
+    /// `self` is at least that of `other`.
+    pub fn dominates(&self, other: &Partition) -> bool {
+        if self.size() != other.size() {
+            return false;
+        }
+        let (mut a, mut b) = (0, 0);
+        (0..self.0.len().max(other.0.len())).all(|i| {
+            a += self.0.get(i).copied().unwrap_or(0);
+            b += other.0.get(i).copied().unwrap_or(0);
+            a >= b
+        })
+    }
+
+    /// Compare in dominance order; `None` if incomparable or of different sizes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use rusty_combinatorial::partitions::partition::Partition;
+    /// let p = |v: Vec<usize>| Partition::new(v).unwrap();
+    /// assert_eq!(p(vec![3, 1]).dominance_cmp(&p(vec![2, 2])), Some(Ordering::Greater));
+    /// assert_eq!(p(vec![3, 1, 1, 1]).dominance_cmp(&p(vec![2, 2, 2])), None);
+    /// ```
+    pub fn dominance_cmp(&self, other: &Partition) -> Option<Ordering> {
+        match (self.dominates(other), other.dominates(self)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Greater),
+            (false, true) => Some(Ordering::Less),
+            (false, false) => None,
+        }
+    }
+
+    /// The Ferrers diagram as rows of cells, as taken by
+    /// [`hook_length`](crate::young::hook::hook_length).
+    pub fn ferrers_diagram(&self) -> Vec<Vec<bool>> {
+        self.0.iter().map(|&x| vec![true; x]).collect()
+    }
+
+    /// Glaisher's bijection from odd parts to distinct parts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::partitions::partition::Partition;
+    /// let odd = Partition::new(vec![3, 3, 3, 1]).unwrap();
+    /// assert_eq!(odd.glaisher_odd_to_distinct().unwrap().as_slice(), &[6, 3, 1]);
+    /// ```
+    pub fn glaisher_odd_to_distinct(&self) -> Result<Partition, PartitionError> {
+        if self.0.iter().any(|x| x % 2 == 0) {
+            return Err(PartitionError::NotOddParts);
+        }
+        let mut parts = Vec::new();
+        for (o, m) in run_lengths(&self.0) {
+            parts.extend((0..usize::BITS).filter(|&k| m >> k & 1 == 1).map(|k| o << k));
+        }
+        parts.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(Partition(parts))
+    }
+
+    /// Inverse of [`Partition::glaisher_odd_to_distinct`].
+    pub fn glaisher_distinct_to_odd(&self) -> Result<Partition, PartitionError> {
+        self.check_distinct()?;
+        let mut parts: Vec<usize> = self
+            .0
+            .iter()
+            .flat_map(|&d| {
+                let k = d.trailing_zeros();
+                std::iter::repeat_n(d >> k, 1 << k)
+            })
+            .collect();
+        parts.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(Partition(parts))
+    }
+
+    /// Sylvester's bijection from odd parts to distinct parts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::partitions::partition::Partition;
+    /// let odd = Partition::new(vec![3, 3, 1]).unwrap();
+    /// let distinct = odd.sylvester_odd_to_distinct().unwrap();
+    /// assert_eq!(distinct.as_slice(), &[4, 2, 1]);
+    /// assert_eq!(distinct.sylvester_distinct_to_odd().unwrap(), odd);
+    /// ```
+    pub fn sylvester_odd_to_distinct(&self) -> Result<Partition, PartitionError> {
+        if self.0.iter().any(|x| x % 2 == 0) {
+            return Err(PartitionError::NotOddParts);
+        }
+        let alpha: Vec<usize> = self.0.iter().map(|x| x / 2).collect();
+        Ok(Partition(sylvester_hooks(&alpha)))
+    }
+
+    /// Inverse of [`Partition::sylvester_odd_to_distinct`].
+    pub fn sylvester_distinct_to_odd(&self) -> Result<Partition, PartitionError> {
+        self.check_distinct()?;
+        // the last hook ends on s_r = 0, so the sums telescope to s₀ = ℓ
+        let ell: i64 = self
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, &h)| if i % 2 == 0 { h as i64 } else { -(h as i64) })
+            .sum();
+        // sᵢ = hᵢ − sᵢ₋₁
+        let mut s = vec![ell];
+        for &h in &self.0 {
+            s.push(h as i64 - s[s.len() - 1]);
+        }
+        let at = |i: usize, shift: usize| (s[i] + shift as i64) as usize;
+        let rows: Vec<usize> = (1..s.len()).step_by(2).map(|i| at(i, (i - 1) / 2)).collect();
+        let cols: Vec<usize> = (2..s.len()).step_by(2).map(|i| at(i, i / 2)).collect();
+        // later rows are counted from the columns, which do not increase
+        let mut tall = cols.len();
+        let parts = (0..ell as usize)
+            .map(|j| {
+                let a = match rows.get(j) {
+                    Some(&r) => r,
+                    None => {
+                        while tall > 0 && cols[tall - 1] <= j {
+                            tall -= 1;
+                        }
+                        tall
+                    }
+                };
+                2 * a + 1
+            })
+            .collect();
+        Ok(Partition(parts))
+    }
+
+    fn check_distinct(&self) -> Result<(), PartitionError> {
+        if self.0.windows(2).any(|w| w[0] == w[1]) {
+            return Err(PartitionError::NotDistinctParts);
+        }
+        Ok(())
+    }
+}
+
+/// Positive hooks `sᵢ₋₁ + sᵢ` of the symmetric diagram of the odd parts `2αᵢ + 1`.
+fn sylvester_hooks(alpha: &[usize]) -> Vec<usize> {
+    let ell = alpha.len() as i64;
+    let conj = Partition(alpha.iter().copied().filter(|&a| a > 0).collect()).conjugate().0;
+    let at = |v: &[usize], j: usize| v.get(j).copied().unwrap_or(0) as i64;
+    let s = |i: usize| -> i64 {
+        match i {
+            0 => ell,
+            _ if i % 2 == 1 => at(alpha, i / 2) - (i / 2) as i64,
+            _ => at(&conj, i / 2 - 1) - (i / 2) as i64,
+        }
+    };
+    (1..)
+        .map(|i| s(i - 1) + s(i))
+        .take_while(|&h| h > 0)
+        .map(|h| h as usize)
+        .collect()
+}
+
+/// `(value, multiplicity)` pairs of a sorted slice.
+fn run_lengths(parts: &[usize]) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for &x in parts {
+        match runs.last_mut() {
+            Some((v, m)) if *v == x => *m += 1,
+            _ => runs.push((x, 1)),
+        }
+    }
+    runs
+}
+
+impl Deref for Partition {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl AsRef<[usize]> for Partition {
+    fn as_ref(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<usize>> for Partition {
+    type Error = PartitionError;
+
+    fn try_from(v: Vec<usize>) -> Result<Self, Self::Error> {
+        Partition::new(v)
+    }
+}
+
+impl From<Partition> for Vec<usize> {
+    fn from(p: Partition) -> Vec<usize> {
+        p.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partitions::generate::generate_partitions;
+    use crate::young::hook::hook_length;
+    use std::collections::HashSet;
+
+    fn all(n: usize) -> Vec<Partition> {
+        generate_partitions(n).into_iter().map(|p| Partition::new(p).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_conjugate_frobenius_and_dominance() {
+        for n in 0..=12 {
+            for p in all(n) {
+                let c = p.conjugate();
+                assert_eq!(c.size(), n);
+                assert_eq!(c.conjugate(), p);
+                assert_eq!(c.durfee_size(), p.durfee_size());
+                let (a, b) = p.frobenius();
+                assert_eq!(c.frobenius(), (b.clone(), a.clone()));
+                assert_eq!(Partition::from_frobenius(&a, &b).unwrap(), p);
+                // conjugation reverses dominance
+                for q in all(n) {
+                    assert_eq!(p.dominance_cmp(&q), q.conjugate().dominance_cmp(&c));
+                }
+            }
+        }
+        let p = Partition::new(vec![3, 2]).unwrap();
+        assert_eq!(hook_length(&p.ferrers_diagram()), 5);
+        assert_eq!(Partition::new(vec![1, 2]), Err(PartitionError::NotAPartition));
+    }
+
+    #[test]
+    fn test_glaisher_and_sylvester_are_bijections() {
+        for n in 0..=22 {
+            let odd: Vec<Partition> = all(n).into_iter().filter(|p| p.iter().all(|x| x % 2 == 1)).collect();
+            let distinct: HashSet<Partition> =
+                all(n).into_iter().filter(|p| p.windows(2).all(|w| w[0] > w[1])).collect();
+            for map in [Partition::glaisher_odd_to_distinct, Partition::sylvester_odd_to_distinct] {
+                let images: HashSet<Partition> = odd.iter().map(|p| map(p).unwrap()).collect();
+                assert_eq!(images, distinct);
+            }
+            for p in &odd {
+                let g = p.glaisher_odd_to_distinct().unwrap();
+                assert_eq!(g.glaisher_distinct_to_odd().unwrap(), *p);
+                let s = p.sylvester_odd_to_distinct().unwrap();
+                assert_eq!(s.sylvester_distinct_to_odd().unwrap(), *p);
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! # Chapter 6: Integer Partitions — Random Generation
+//!
+//! Sample integer partitions of `n` uniformly at random.
+//!
+//! ## Algorithm Description
+//!
+//! **RANPAR** (Nijenhuis–Wilf): from the identity
+//!   `m · p(m) = Σ_{d ≥ 1} Σ_{j ≥ 1, jd ≤ m} d · p(m − jd)`, pick a pair
+//!   `(d, j)` with probability `d · p(m − jd) / (m · p(m))`, append `j` copies
+//!   of `d`, and continue with `m − jd`. The result is exactly uniform.
+//! **Boltzmann sampler**: with `x = exp(−π / √(6n))`, draw each multiplicity
+//!   `m_k` independently from the geometric law `P(m_k = t) = (1 − xᵏ) x^{kt}`.
+//!   Every partition of a given size is equally likely, so rejecting samples
+//!   whose size is not `n` leaves an exactly uniform partition of `n`, without
+//!   any big-integer table. The parameter makes size `n` the expected size.
+//!   Instead of drawing `m₁` and hoping the size hits `n`, the sampler sets
+//!   `m₁` to the gap `r` and accepts with probability `xʳ` (probabilistic
+//!   divide-and-conquer, Arratia–DeSalvo), which is exact and rejects far less.
+//! **Fixed number of parts**: partitions of `n` into exactly `k` parts either
+//!   contain a part `1` (remove it: `(n−1, k−1)`) or have all parts `≥ 2`
+//!   (subtract one from each: `(n−k, k)`). Walking this recurrence with the
+//!   exact counts as weights, then replaying it backwards, gives a uniform
+//!   sample.
+//!
+//! ## Complexity
+//!
+//! - RANPAR: O(n^1.5) to tabulate `p(0..=n)`, then O(m log m) big-integer
+//!   steps per block of equal parts drawn
+//! - Boltzmann: O(n) per attempt, O(n^{1/4}) expected attempts
+//! - Fixed parts: O(n · k) big-integer table, O(n) steps
+
+use rand::distributions::Open01;
+use rand::Rng;
+
+use crate::arith::biguint::BigUint;
+
+use super::count::partition_numbers;
+
+/// Uniformly random partition of `n` (RANPAR), non-increasing.
+///
+/// Uses `rand::thread_rng()`; see [`random_partition_with`] for a seedable
+/// variant.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::random::random_partition;
+/// let p = random_partition(20);
+/// assert_eq!(p.iter().sum::<usize>(), 20);
+/// assert!(p.windows(2).all(|w| w[0] >= w[1]));
+/// ```
+pub fn random_partition(n: usize) -> Vec<usize> {
+    random_partition_with(n, &mut rand::thread_rng())
+}
+
+/// Uniformly random partition of `n` (RANPAR) using the given RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::partitions::random::random_partition_with;
+/// let a = random_partition_with(50, &mut StdRng::seed_from_u64(9));
+/// let b = random_partition_with(50, &mut StdRng::seed_from_u64(9));
+/// assert_eq!(a, b);
+/// ```
+pub fn random_partition_with<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    let p = partition_numbers(n);
+    let mut parts = Vec::new();
+    let mut m = n;
+    while m > 0 {
+        let mut r = BigUint::random_below(&p[m].mul_small(m as u64), rng);
+        'pick: for d in 1..=m {
+            for j in 1..=m / d {
+                let w = p[m - j * d].mul_small(d as u64);
+                if r < w {
+                    parts.extend(std::iter::repeat_n(d, j));
+                    m -= j * d;
+                    break 'pick;
+                }
+                r = &r - &w;
+            }
+        }
+    }
+    parts.sort_unstable_by(|a, b| b.cmp(a));
+    parts
+}
+
+/// Uniformly random partition of `n` by a Boltzmann sampler with rejection.
+///
+/// Uses `rand::thread_rng()`; see [`boltzmann_partition_with`] for a seedable
+/// variant.
+pub fn boltzmann_partition(n: usize) -> Vec<usize> {
+    boltzmann_partition_with(n, &mut rand::thread_rng())
+}
+
+/// Uniformly random partition of `n` by a Boltzmann sampler with rejection,
+/// using the given RNG.
+///
+/// Needs no table of partition numbers, so it scales to `n` where
+/// [`random_partition_with`] becomes expensive.
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::partitions::random::boltzmann_partition_with;
+/// let p = boltzmann_partition_with(2_000, &mut StdRng::seed_from_u64(1));
+/// assert_eq!(p.iter().sum::<usize>(), 2_000);
+/// ```
+pub fn boltzmann_partition_with<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let ln_x = -std::f64::consts::PI / (6.0 * n as f64).sqrt();
+    let mut mult = vec![0usize; n + 1];
+    loop {
+        let mut total = 0;
+        for k in (2..=n).rev() {
+            // geometric with success probability 1 − xᵏ, by inversion
+            let u: f64 = rng.sample(Open01);
+            let t = (u.ln() / (k as f64 * ln_x)).floor();
+            mult[k] = if t > n as f64 { n + 1 } else { t as usize };
+            total += k * mult[k];
+            if total > n {
+                break;
+            }
+        }
+        if total > n {
+            continue;
+        }
+        // ones fill the gap; P(m₁ = r) ∝ xʳ, so accept with probability xʳ
+        mult[1] = n - total;
+        let u: f64 = rng.sample(Open01);
+        if u.ln() < mult[1] as f64 * ln_x {
+            return (1..=n)
+                .rev()
+                .flat_map(|k| std::iter::repeat_n(k, mult[k]))
+                .collect();
+        }
+    }
+}
+
+/// Uniformly random partition of `n` into exactly `k` parts, non-increasing.
+///
+/// Uses `rand::thread_rng()`; see [`random_partition_with_parts_with`] for a
+/// seedable variant.
+///
+/// # Panics
+///
+/// Panics if no such partition exists (`k > n`, or `k == 0 < n`).
+pub fn random_partition_with_parts(n: usize, k: usize) -> Vec<usize> {
+    random_partition_with_parts_with(n, k, &mut rand::thread_rng())
+}
+
+/// Uniformly random partition of `n` into exactly `k` parts using the given
+/// RNG.
+///
+/// # Panics
+///
+/// Panics if no such partition exists (`k > n`, or `k == 0 < n`).
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::partitions::random::random_partition_with_parts_with;
+/// let p = random_partition_with_parts_with(100, 7, &mut StdRng::seed_from_u64(2));
+/// assert_eq!(p.len(), 7);
+/// assert_eq!(p.iter().sum::<usize>(), 100);
+/// ```
+pub fn random_partition_with_parts_with<R: Rng + ?Sized>(
+    n: usize,
+    k: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    assert!(k <= n && (k > 0 || n == 0), "no partition of {} into {} parts", n, k);
+    // e[i][j] = partitions of i into exactly j parts
+    let mut e = vec![vec![BigUint::zero(); k + 1]; n + 1];
+    e[0][0] = BigUint::one();
+    for i in 1..=n {
+        for j in 1..=k.min(i) {
+            e[i][j] = &e[i - 1][j - 1] + &e[i - j][j];
+        }
+    }
+
+    // walk down to (0, 0), remembering which branch was taken
+    let mut has_one = Vec::with_capacity(n);
+    let (mut i, mut j) = (n, k);
+    while i > 0 {
+        let r = BigUint::random_below(&e[i][j], rng);
+        let one = r < e[i - 1][j - 1];
+        has_one.push(one);
+        if one {
+            i -= 1;
+            j -= 1;
+        } else {
+            i -= j;
+        }
+    }
+    let mut parts = Vec::with_capacity(k);
+    for one in has_one.into_iter().rev() {
+        if one {
+            parts.push(1);
+        } else {
+            parts.iter_mut().for_each(|x| *x += 1);
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partitions::generate::generate_partitions;
+    use crate::partitions::rank::rank_partition;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn histogram(n: usize, trials: usize, mut sample: impl FnMut() -> Vec<usize>) -> Vec<usize> {
+        let mut hits = vec![0; generate_partitions(n).len()];
+        for _ in 0..trials {
+            hits[rank_partition(&sample(), n)] += 1;
+        }
+        hits
+    }
+
+    #[test]
+    fn test_samplers_are_roughly_uniform() {
+        let mut rng = StdRng::seed_from_u64(21);
+        // p(7) = 15, expected 1000 hits each
+        let exact = histogram(7, 15_000, || random_partition_with(7, &mut rng));
+        let boltz = histogram(7, 15_000, || boltzmann_partition_with(7, &mut rng));
+        for h in exact.iter().chain(&boltz) {
+            assert!((870..1130).contains(h), "{:?} / {:?}", exact, boltz);
+        }
+        // partitions of 10 into 3 parts: 8 of them, expected 1000 hits each
+        let mut hits = [0usize; 8];
+        let three: Vec<Vec<usize>> = generate_partitions(10).into_iter().filter(|p| p.len() == 3).collect();
+        for _ in 0..8000 {
+            let p = random_partition_with_parts_with(10, 3, &mut rng);
+            hits[three.iter().position(|q| *q == p).unwrap()] += 1;
+        }
+        assert!(hits.iter().all(|h| (870..1130).contains(h)), "{:?}", hits);
+    }
+
+    #[test]
+    fn test_random_partition_seeded_fixture() {
+        let p = random_partition_with(12, &mut StdRng::seed_from_u64(42));
+        assert_eq!(p, vec![4, 1, 1, 1, 1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no partition of 100000 into 100001 parts")]
+    fn test_parts_sampler_rejects_before_building_table() {
+        // the table alone would hold 10^10 entries
+        random_partition_with_parts_with(100_000, 100_001, &mut StdRng::seed_from_u64(0));
+    }
+}
@@ -1,42 +1,84 @@
 //! # Chapter 6: Integer Partitions — Ranking
 //!
-//! Assign a lexicographic rank to an integer partition of `n` using dynamic
+//! Assign a rank to an integer partition of `n` using dynamic
 //! programming table of partition counts.
 //!
+//! Partitions are ordered as [`generate_partitions`](super::generate::generate_partitions)
+//! lists them: reverse lexicographic on the non-increasing part sequence, so
+//! rank 0 is `[n]` and the last rank is `[1, 1, …, 1]`.
+//!
 //! ## Algorithm Description
 //!
-//! 1. Build table `P[i][j]` = number of partitions of `i` with maximum part `j`.
-//! 2. To rank partition `[p₁, p₂, …]`, for each part `pₖ`, count how many partitions
-//!    start with smaller first part, then recurse on remainder.
+//! 1. Build table `P[i][j]` = number of partitions of `i` with parts ≤ `j`
+//!    ([`checked_bounded_partition_table`]).
+//! 2. To rank partition `[p₁, p₂, …]`, for each part `pₖ`, count the partitions
+//!    of the remainder that continue with a larger part `v ≤ pₖ₋₁` instead
+//!    (`P[rem - v][v]` each), then recurse on the remainder.
+//! 3. Unranking walks the same blocks from the largest candidate part down.
+//!
+//! `p(n)` outgrows `u64` from `n = 417`, yet the first ranks of any `n` stay
+//! small, so the table marks overflowing entries `None` instead of giving up.
+//! A rank that needs one surfaces as [`RankError::Overflow`]; unranking
+//! places a rank with [`find_block`], which treats such a block as containing
+//! it.
 //!
 //! ## Complexity
 //!
 //! - Time: O(n²)
-//! - Space: O(n²)
-//!
-//! Rank integer partitions in lexicographic order (first parts compared first).
-//! Uses partition table P[i][j] = number of partitions of i with parts ≤ j.
-//!
-/// Build the partition count table P(i,j) for 0..=n
-///
-/// `P[i][j]` = count of partitions of `i` using parts ≤ `j`.
-
-fn partition_table(n: usize) -> Vec<Vec<usize>> {
-    let mut p = vec![vec![0usize; n + 1]; n + 1];
-    p[0].iter_mut().for_each(|x| *x = 1);
-    for i in 1..=n {
-        for j in 1..=n {
-            p[i][j] = p[i][j - 1];
-            if j <= i {
-                p[i][j] = p[i][j].saturating_add(p[i - j][j]);
-            }
-        }
-    }
-    p
+//! - Space: O(n²) for ranking, O(n) for counting
+
+use crate::arith::biguint::BigUint;
+use crate::arith::rank_int::{find_block, RankError, RankInt};
+
+use super::count::{bounded_partition_count, checked_bounded_partition_table, partition_number};
+
+/// The partition number `p(n)`, or `None` if it does not fit in `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::rank::checked_partition_count;
+/// assert_eq!(checked_partition_count(10), Some(42));
+/// assert_eq!(checked_partition_count(500), None);
+/// ```
+pub fn checked_partition_count(n: usize) -> Option<usize> {
+    bounded_partition_count(n, n).ok()
 }
 
+/// The number `p(n, k)` of partitions of `n` into parts of size at most `k`
+/// (equivalently, into at most `k` parts), or `None` if it does not fit in
+/// `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::rank::checked_partition_count_bounded;
+/// assert_eq!(checked_partition_count_bounded(6, 2), Some(4)); // 222 2211 21111 111111
+/// ```
+pub fn checked_partition_count_bounded(n: usize, k: usize) -> Option<usize> {
+    bounded_partition_count(n, k).ok()
+}
+
+/// The partition number `p(n)` as a [`BigUint`], via the pentagonal
+/// recurrence in [`partition_number`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::rank::partition_count_big;
+/// assert_eq!(partition_count_big(100).to_string(), "190569292");
+/// ```
+pub fn partition_count_big(n: usize) -> BigUint {
+    partition_number(n)
+}
+
+/// The number `p(n, k)` of partitions of `n` into parts of size at most `k`, as
+/// a [`BigUint`].
+pub fn partition_count_bounded_big(n: usize, k: usize) -> BigUint {
+    bounded_partition_count(n, k).expect("BigUint arithmetic does not overflow")
+}
 
-/// Compute the lexicographic rank of a partition of `n`.
+/// Compute the rank of a partition of `n`.
 ///
 /// # Arguments
 ///
@@ -45,41 +87,117 @@ fn partition_table(n: usize) -> Vec<Vec<usize>> {
 ///
 /// # Returns
 ///
-/// The 0‐based lex rank among all partitions of `n`.
+/// The 0‐based position of `part` in [`generate_partitions`](super::generate::generate_partitions)
+/// order.
+///
+/// # Panics
+///
+/// Panics if `part` is not a partition of `n`, or if the rank does not fit in
+/// `usize`; see [`try_rank_partition`].
 ///
 /// # Example
 ///
 /// ```
 /// use rusty_combinatorial::partitions::rank::rank_partition;
-/// let rank = rank_partition(&[2, 2], 4);
-/// assert!(rank < 5); // there are 5 partitions of 4
+/// assert_eq!(rank_partition(&[4], 4), 0);
+/// assert_eq!(rank_partition(&[2, 2], 4), 2);
+/// assert_eq!(rank_partition(&[1, 1, 1, 1], 4), 4);
 /// ```
-
 pub fn rank_partition(part: &[usize], n: usize) -> usize {
-    let p = partition_table(n);
-    let mut rank = 0usize;
+    match try_rank_partition(part, n) {
+        Ok(rank) => rank,
+        Err(RankError::InvalidInput) => panic!("{part:?} is not a partition of {n}"),
+        Err(e) => panic!("partition rank overflows usize: {e:?}"),
+    }
+}
+
+/// Compute the rank of a partition of `n` in any [`RankInt`] type.
+///
+/// # Returns
+///
+/// `Err(RankError::InvalidInput)` if `part` is not a non-increasing sequence
+/// of positive integers summing to `n`; `Err(RankError::Overflow)` if the
+/// rank does not fit in `R`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::arith::biguint::BigUint;
+/// use rusty_combinatorial::partitions::rank::try_rank_partition;
+/// let ones = vec![1; 500];
+/// let r: BigUint = try_rank_partition(&ones, 500).unwrap();
+/// assert_eq!(r.to_string(), "2300165032574323995026"); // p(500) - 1
+/// assert!(try_rank_partition::<u64>(&ones, 500).is_err());
+/// ```
+pub fn try_rank_partition<R: RankInt>(part: &[usize], n: usize) -> Result<R, RankError> {
+    let valid = part.iter().all(|&x| x > 0)
+        && part.windows(2).all(|w| w[0] >= w[1])
+        && part.iter().sum::<usize>() == n;
+    if !valid {
+        return Err(RankError::InvalidInput);
+    }
+    let p = checked_bounded_partition_table::<R>(n, n);
+    let mut rank = R::zero();
     let mut rem = n;
     let mut prev_max = n;
 
     for &x in part {
-        // valid values for this part are in 1..=prev_max and must sum to rem
-        // We count partitions whose current part v is > x (i.e., v in (x+1)..=prev_max).
-        if x + 1 <= prev_max {
-            for v in (x + 1)..=prev_max {
-                if v <= rem {
-                    rank = rank.saturating_add(p[rem - v][v]);
-                }
-            }
+        // count partitions whose current part v is > x (i.e., v in (x+1)..=prev_max)
+        for v in (x + 1)..=prev_max.min(rem) {
+            let block = p[rem - v][v].as_ref().ok_or(RankError::Overflow)?;
+            rank = rank.checked_add(block).ok_or(RankError::Overflow)?;
         }
-        rem = rem - x;
+        rem -= x;
         prev_max = x;
     }
-    rank
+    Ok(rank)
+}
+
+/// The partition of `n` with the given rank, inverse of [`rank_partition`].
+///
+/// # Panics
+///
+/// Panics if `rank ≥ p(n)`; see [`try_unrank_partition`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::rank::unrank_partition;
+/// assert_eq!(unrank_partition(2, 4), vec![2, 2]);
+/// ```
+pub fn unrank_partition(rank: usize, n: usize) -> Vec<usize> {
+    try_unrank_partition(&rank, n).expect("partition rank out of range")
+}
+
+/// The partition of `n` with the given [`RankInt`] rank.
+///
+/// # Returns
+///
+/// `Err(RankError::OutOfRange)` if `rank ≥ p(n)`.
+pub fn try_unrank_partition<R: RankInt>(rank: &R, n: usize) -> Result<Vec<usize>, RankError> {
+    let p = checked_bounded_partition_table::<R>(n, n);
+    if p[n][n].as_ref().is_some_and(|total| rank >= total) {
+        return Err(RankError::OutOfRange);
+    }
+    let mut rank = rank.clone();
+    let mut part = Vec::new();
+    let mut rem = n;
+    let mut prev_max = n;
+    while rem > 0 {
+        let top = prev_max.min(rem);
+        let blocks = (1..=top).rev().map(|v| p[rem - v][v].as_ref());
+        let v = top - find_block(&mut rank, blocks).ok_or(RankError::OutOfRange)?;
+        part.push(v);
+        rem -= v;
+        prev_max = v;
+    }
+    Ok(part)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::partitions::generate::generate_partitions;
 
     #[test]
     fn test_rank_partition_simple() {
@@ -88,5 +206,45 @@ mod tests {
         let r2 = rank_partition(&[2, 2], 4);
         assert!(r0 < r1 && r1 < r2);
     }
-}
 
+    #[test]
+    fn test_rank_unrank_roundtrip_against_generation() {
+        for n in 0..=15 {
+            let all = generate_partitions(n);
+            assert_eq!(checked_partition_count(n), Some(all.len()));
+            for (r, part) in all.iter().enumerate() {
+                assert_eq!(rank_partition(part, n), r);
+                assert_eq!(unrank_partition(r, n), *part);
+            }
+            for k in 0..=n {
+                let bounded = all.iter().filter(|p| p.iter().all(|&x| x <= k)).count();
+                assert_eq!(checked_partition_count_bounded(n, k), Some(bounded));
+            }
+            assert_eq!(try_unrank_partition(&all.len(), n), Err(RankError::OutOfRange));
+        }
+        assert_eq!(try_rank_partition::<usize>(&[1, 2], 3), Err(RankError::InvalidInput));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a partition of 3")]
+    fn test_rank_partition_rejects_invalid_input() {
+        rank_partition(&[1, 2], 3);
+    }
+
+    #[test]
+    fn test_large_n_ranks_are_exact() {
+        // p(416) < 2^64 ≤ p(417)
+        assert!(checked_partition_count(416).is_some());
+        assert!(checked_partition_count(417).is_none());
+        let n = 600;
+        let total = partition_count_big(n);
+        let last = total.checked_sub(&BigUint::one()).unwrap();
+        let ones = vec![1; n];
+        assert_eq!(try_rank_partition::<BigUint>(&ones, n).unwrap(), last);
+        assert_eq!(try_unrank_partition(&last, n).unwrap(), ones);
+        // small ranks of a huge n still fit in usize
+        assert_eq!(rank_partition(&[n - 1, 1], n), 1);
+        assert_eq!(unrank_partition(1, n), vec![n - 1, 1]);
+        assert_eq!(try_rank_partition::<usize>(&ones, n), Err(RankError::Overflow));
+    }
+}
@@ -0,0 +1,437 @@
+//! # Chapter 6: Integer Partitions — Restricted Partitions
+//!
+//! Lazily enumerate the partitions of `n` that satisfy a set of constraints, in
+//! reverse lexicographic order (`[n]` first, `[1, 1, …, 1]` last), the same
+//! order as [`generate_partitions`](super::generate::generate_partitions).
+//!
+//! ## Algorithm Description
+//!
+//! The constraints reduce to a sorted list of usable part sizes
+//! `a₀ < a₁ < … < a_{t-1}` (bounded by the maximum part, filtered by odd /
+//! allowed sets), a distinctness flag, and a range for the number of parts.
+//!
+//! 1. Build one table of ranges `[L, H][u][r]`, the fewest and most parts in a
+//!    partition of `r` drawn from the `u` smallest sizes (each at most once if
+//!    distinct), or none if `r` has no such partition.
+//! 2. **Fill**: from a prefix, repeatedly append the largest usable part whose
+//!    remainder can still be completed with a number of parts in range, i.e.
+//!    one where `[L, H]` meets the parts still allowed.
+//! 3. **Successor**: from the right, find the last part that can be lowered to
+//!    a smaller usable size with a completable remainder, lower it, and fill.
+//!
+//! When every count in `[L, H]` is achievable, as it is for contiguous sizes
+//! with or without distinctness, no dead branches are explored. Odd or
+//! allowed part sets combined with a part-count bound can leave gaps in that
+//! range; a fill that runs into one backtracks through the successor step.
+//!
+//! [`PartitionConstraints::count`] instead counts by part number, rolling a
+//! `(parts, remainder)` table over the sizes so only one layer is kept. Counts
+//! only ever grow as sizes are added, so a cell that overflows marks every
+//! count built from it as too large, and the final count is `None` exactly
+//! when it does not fit in `usize`.
+//!
+//! ## Complexity
+//!
+//! - Table: O(t · n) time and space
+//! - Successor: O(ℓ · t) where ℓ is the number of parts, without gaps
+//! - Count: O(t · n · k) time, O(n · k) space (`k = 1` without a part-count
+//!   bound)
+
+/// Constraints on the partitions produced by [`RestrictedPartitions`].
+///
+/// Constraints combine: a part size must satisfy all of them to be used.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::restricted::PartitionConstraints;
+/// // partitions of 10 into exactly 3 distinct odd parts... none exist
+/// let c = PartitionConstraints::new().exact_parts(3).distinct().odd_parts();
+/// assert_eq!(c.count(10), Some(0));
+/// // ...but 15 = 11+3+1 = 9+5+1 = 7+5+3
+/// let all: Vec<Vec<usize>> = c.iter(15).collect();
+/// assert_eq!(all, vec![vec![11, 3, 1], vec![9, 5, 1], vec![7, 5, 3]]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PartitionConstraints {
+    min_parts: usize,
+    max_parts: Option<usize>,
+    max_part: Option<usize>,
+    distinct: bool,
+    odd: bool,
+    allowed: Option<Vec<usize>>,
+}
+
+impl PartitionConstraints {
+    /// No constraints: every partition of `n`.
+    pub fn new() -> Self {
+        PartitionConstraints::default()
+    }
+
+    /// Exactly `k` parts.
+    pub fn exact_parts(mut self, k: usize) -> Self {
+        self.min_parts = k;
+        self.max_parts = Some(k);
+        self
+    }
+
+    /// At most `k` parts.
+    pub fn max_parts(mut self, k: usize) -> Self {
+        self.min_parts = 0;
+        self.max_parts = Some(k);
+        self
+    }
+
+    /// Every part is at most `m`.
+    pub fn max_part(mut self, m: usize) -> Self {
+        self.max_part = Some(m);
+        self
+    }
+
+    /// No part is repeated.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Every part is odd.
+    pub fn odd_parts(mut self) -> Self {
+        self.odd = true;
+        self
+    }
+
+    /// Every part is taken from `parts` (in any order, duplicates ignored).
+    pub fn allowed_parts(mut self, parts: &[usize]) -> Self {
+        self.allowed = Some(parts.to_vec());
+        self
+    }
+
+    /// Iterate over the constrained partitions of `n` in reverse lex order.
+    pub fn iter(&self, n: usize) -> RestrictedPartitions {
+        RestrictedPartitions::new(n, self)
+    }
+
+    /// Number of constrained partitions of `n`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the count does not fit in `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::partitions::restricted::PartitionConstraints;
+    /// assert_eq!(PartitionConstraints::new().count(100), Some(190569292));
+    /// assert_eq!(PartitionConstraints::new().count(500), None);
+    /// ```
+    pub fn count(&self, n: usize) -> Option<usize> {
+        let sizes = usable_sizes(self, n);
+        // No partition of `n` has more than `n` parts.
+        let Some(k) = self.max_parts.map(|k| k.min(n)) else {
+            let mut ways = vec![Some(0); n + 1];
+            ways[0] = Some(1);
+            for &a in &sizes {
+                add_part(&mut ways, a, self.distinct);
+            }
+            return ways[n];
+        };
+        if self.min_parts > k {
+            return Some(0);
+        }
+        // ways[j][r]: partitions of r into j parts from the sizes seen so far
+        let mut ways = vec![vec![Some(0); n + 1]; k + 1];
+        ways[0][0] = Some(1);
+        for &a in &sizes {
+            for step in 1..=k {
+                // layer j - 1 must already hold parts of size `a` unless distinct
+                let j = if self.distinct { k + 1 - step } else { step };
+                for r in a..=n {
+                    ways[j][r] = checked_sum(ways[j][r], ways[j - 1][r - a]);
+                }
+            }
+        }
+        ways[self.min_parts..=k].iter().try_fold(0usize, |acc, w| acc.checked_add(w[n]?))
+    }
+}
+
+/// Add parts of size `a` to a table of partition counts, at most once each if
+/// `distinct`.
+fn add_part(ways: &mut [Option<usize>], a: usize, distinct: bool) {
+    let n = ways.len() - 1;
+    if distinct {
+        for r in (a..=n).rev() {
+            ways[r] = checked_sum(ways[r], ways[r - a]);
+        }
+    } else {
+        for r in a..=n {
+            ways[r] = checked_sum(ways[r], ways[r - a]);
+        }
+    }
+}
+
+/// `x + y`, or `None` if either is already too large or the sum overflows.
+fn checked_sum(x: Option<usize>, y: Option<usize>) -> Option<usize> {
+    x?.checked_add(y?)
+}
+
+/// Part sizes allowed by `c` for partitions of `n`, increasing.
+fn usable_sizes(c: &PartitionConstraints, n: usize) -> Vec<usize> {
+    let cap = c.max_part.unwrap_or(n).min(n);
+    let mut sizes: Vec<usize> = match &c.allowed {
+        Some(a) => a.iter().copied().filter(|&s| s >= 1 && s <= cap).collect(),
+        None => (1..=cap).collect(),
+    };
+    sizes.retain(|s| !c.odd || s % 2 == 1);
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes
+}
+
+/// Lazy iterator over restricted partitions; see [`PartitionConstraints`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::partitions::restricted::PartitionConstraints;
+/// let mut it = PartitionConstraints::new().max_part(2).iter(4);
+/// assert_eq!(it.next_slice(), Some(&[2, 2][..]));
+/// assert_eq!(it.next_slice(), Some(&[2, 1, 1][..]));
+/// assert_eq!(it.next_slice(), Some(&[1, 1, 1, 1][..]));
+/// assert_eq!(it.next_slice(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RestrictedPartitions {
+    n: usize,
+    /// Usable part sizes, increasing.
+    sizes: Vec<usize>,
+    distinct: bool,
+    min_parts: usize,
+    max_parts: usize,
+    /// `[L, H][u][r]` as `(fewest, most)` parts, flattened; `None` when `r`
+    /// has no partition into the `u` smallest sizes.
+    span: Vec<Option<(usize, usize)>>,
+    /// Indices into `sizes` of the current partition's parts.
+    idx: Vec<usize>,
+    parts: Vec<usize>,
+    /// `n` minus the sum of `parts`.
+    rem: usize,
+    started: bool,
+    done: bool,
+}
+
+impl RestrictedPartitions {
+    fn new(n: usize, c: &PartitionConstraints) -> Self {
+        let sizes = usable_sizes(c, n);
+        let t = sizes.len();
+        let mut it = RestrictedPartitions {
+            n,
+            sizes,
+            distinct: c.distinct,
+            min_parts: c.min_parts,
+            max_parts: c.max_parts.unwrap_or(usize::MAX),
+            span: Vec::with_capacity((t + 1) * (n + 1)),
+            idx: Vec::new(),
+            parts: Vec::new(),
+            rem: n,
+            started: false,
+            done: false,
+        };
+        it.build_table();
+        let found = it.completable(n, t, 0) && (it.fill(t) || it.advance());
+        it.done = !found;
+        it
+    }
+
+    fn at(&self, u: usize, r: usize) -> usize {
+        u * (self.n + 1) + r
+    }
+
+    /// Fill `span` row by row; row `u` reads only rows `u - 1` and `u`.
+    fn build_table(&mut self) {
+        self.span.push(Some((0, 0)));
+        self.span.resize(self.n + 1, None);
+        for u in 1..=self.sizes.len() {
+            let a = self.sizes[u - 1];
+            let from = if self.distinct { u - 1 } else { u };
+            for r in 0..=self.n {
+                let skip = self.span[self.at(u - 1, r)];
+                let with = if r >= a { self.span[self.at(from, r - a)] } else { None };
+                let cell = match (skip, with) {
+                    (s, None) => s,
+                    (None, Some((lo, hi))) => Some((lo + 1, hi + 1)),
+                    (Some((lo, hi)), Some((wlo, whi))) => Some((lo.min(wlo + 1), hi.max(whi + 1))),
+                };
+                self.span.push(cell);
+            }
+        }
+    }
+
+    /// Whether remainder `rem` may be finished from the `u` smallest sizes when
+    /// `used` parts are already placed, judging the part count by `[L, H]`.
+    fn completable(&self, rem: usize, u: usize, used: usize) -> bool {
+        self.span[self.at(u, rem)]
+            .is_some_and(|(lo, hi)| used + lo <= self.max_parts && used + hi >= self.min_parts)
+    }
+
+    /// Size-index bound for the part after one with index `i`.
+    fn next_bound(&self, i: usize) -> usize {
+        if self.distinct { i } else { i + 1 }
+    }
+
+    /// Try size index `i` as the next part.
+    fn fits(&self, i: usize) -> bool {
+        let a = self.sizes[i];
+        a <= self.rem && self.completable(self.rem - a, self.next_bound(i), self.idx.len() + 1)
+    }
+
+    fn push(&mut self, i: usize) {
+        self.idx.push(i);
+        self.parts.push(self.sizes[i]);
+        self.rem -= self.sizes[i];
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        let i = self.idx.pop()?;
+        self.rem += self.parts.pop().unwrap();
+        Some(i)
+    }
+
+    /// Append the largest feasible parts (size indices below `u`) until the
+    /// remainder is 0, or return `false` at a gap in the part counts.
+    fn fill(&mut self, mut u: usize) -> bool {
+        while self.rem > 0 {
+            let Some(i) = (0..u).rev().find(|&i| self.fits(i)) else {
+                return false;
+            };
+            self.push(i);
+            u = self.next_bound(i);
+        }
+        true
+    }
+
+    /// Move to the next complete partition in reverse lex order.
+    fn advance(&mut self) -> bool {
+        while let Some(i) = self.pop() {
+            if let Some(lower) = (0..i).rev().find(|&l| self.fits(l)) {
+                self.push(lower);
+                if self.fill(self.next_bound(lower)) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Advance to the next partition and lend it as a slice.
+    pub fn next_slice(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            return Some(&self.parts);
+        }
+        if !self.advance() {
+            self.done = true;
+            return None;
+        }
+        Some(&self.parts)
+    }
+}
+
+impl Iterator for RestrictedPartitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        self.next_slice().map(<[usize]>::to_vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partitions::generate::generate_partitions;
+
+    fn brute(n: usize, keep: impl Fn(&[usize]) -> bool) -> Vec<Vec<usize>> {
+        generate_partitions(n).into_iter().filter(|p| keep(p)).collect()
+    }
+
+    #[test]
+    fn test_constraints_match_filtered_generation() {
+        for n in 0..=14 {
+            let all: Vec<Vec<usize>> = PartitionConstraints::new().iter(n).collect();
+            assert_eq!(all, generate_partitions(n));
+            for k in 0..=5 {
+                let c = PartitionConstraints::new().exact_parts(k);
+                assert_eq!(c.iter(n).collect::<Vec<_>>(), brute(n, |p| p.len() == k));
+                assert_eq!(c.count(n), Some(brute(n, |p| p.len() == k).len()));
+                let c = PartitionConstraints::new().max_parts(k).max_part(4);
+                let b = brute(n, |p| p.len() <= k && p.iter().all(|&x| x <= 4));
+                assert_eq!(c.iter(n).collect::<Vec<_>>(), b);
+            }
+            let distinct = |p: &[usize]| p.windows(2).all(|w| w[0] > w[1]);
+            let odd = |p: &[usize]| p.iter().all(|x| x % 2 == 1);
+            let c = PartitionConstraints::new().distinct();
+            assert_eq!(c.iter(n).collect::<Vec<_>>(), brute(n, distinct));
+            // Euler: as many partitions into distinct parts as into odd parts
+            assert_eq!(c.count(n), PartitionConstraints::new().odd_parts().count(n));
+            let c = PartitionConstraints::new().odd_parts().distinct().max_parts(3);
+            let b = brute(n, |p| odd(p) && distinct(p) && p.len() <= 3);
+            assert_eq!(c.iter(n).collect::<Vec<_>>(), b);
+            let c = PartitionConstraints::new().allowed_parts(&[5, 2, 3, 2]);
+            let b = brute(n, |p| p.iter().all(|x| [2, 3, 5].contains(x)));
+            assert_eq!(c.iter(n).collect::<Vec<_>>(), b);
+        }
+    }
+
+    #[test]
+    fn test_huge_part_counts_behave_like_n() {
+        let n = 5;
+        let c = PartitionConstraints::new().max_parts(usize::MAX);
+        assert_eq!(c.iter(n).collect::<Vec<_>>(), generate_partitions(n));
+        assert_eq!(c.count(n), PartitionConstraints::new().max_parts(n).count(n));
+        let c = PartitionConstraints::new().exact_parts(1 << 40);
+        assert_eq!(c.iter(n).count(), 0);
+        assert_eq!(c.count(n), PartitionConstraints::new().exact_parts(n + 1).count(n));
+    }
+
+    #[test]
+    fn test_count_overflow_is_none() {
+        // p(416) < 2^64 ≤ p(417)
+        assert!(PartitionConstraints::new().count(416).is_some());
+        assert_eq!(PartitionConstraints::new().count(417), None);
+        // the part-count path: at most 40 parts first overflows at n = 447
+        let c = PartitionConstraints::new().max_parts(40);
+        assert!(c.count(446).is_some());
+        assert_eq!(c.count(447), None);
+        // q(500) < 2^64, so the distinct path stays exact there
+        assert!(PartitionConstraints::new().distinct().max_parts(40).count(500).is_some());
+    }
+
+    #[test]
+    fn test_part_count_gaps_backtrack() {
+        for n in 0..=24 {
+            for k in 0..=8 {
+                let c = PartitionConstraints::new().allowed_parts(&[2, 5]).exact_parts(k);
+                let b = brute(n, |p| p.len() == k && p.iter().all(|x| [2, 5].contains(x)));
+                assert_eq!(c.iter(n).collect::<Vec<_>>(), b);
+                assert_eq!(c.count(n), Some(b.len()));
+                let c = PartitionConstraints::new().odd_parts().exact_parts(k);
+                let b = brute(n, |p| p.len() == k && p.iter().all(|x| x % 2 == 1));
+                assert_eq!(c.iter(n).collect::<Vec<_>>(), b);
+                assert_eq!(c.count(n), Some(b.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_part_count_bound_starts_lazily() {
+        let mut it = PartitionConstraints::new().max_parts(500).iter(500);
+        assert_eq!(it.next(), Some(vec![500]));
+        assert_eq!(it.next(), Some(vec![499, 1]));
+        let mut it = PartitionConstraints::new().exact_parts(50).iter(1000);
+        let first = it.next().unwrap();
+        assert_eq!(first[0], 951);
+        assert_eq!(first.len(), 50);
+    }
+}
@@ -0,0 +1,245 @@
+//! # Chapter 1: Permutations — Colexicographic Order
+//!
+//! Colex order compares sequences from the **right**: `p` precedes `q` if, at
+//! the last position where they differ, `p` has the smaller entry. Equivalently,
+//! `p` precedes `q` in colex order exactly when `reverse(p)` precedes
+//! `reverse(q)` in lex order, so every lex algorithm has a mirrored twin.
+//!
+//! ## Algorithm Description
+//!
+//! Mirror of [`next_lex_perm`](super::lex::next_lex_perm):
+//!
+//! 1. Scan from the left for the first `j` with `v[j] < v[j-1]`.
+//! 2. Find the leftmost `k < j` with `v[k] > v[j]`.
+//! 3. Swap `v[j]` and `v[k]`, then reverse the prefix `v[..j]`.
+//!
+//! Ranking reads the Lehmer code of the reversed permutation. For multisets,
+//! the colex rank of `p` is the lex rank of `reverse(p)`, so
+//! [`ColexPermutations::range`] and [`Iterator::nth`] seek with
+//! [`unrank_multiset_perm`].
+//!
+//! ## Complexity
+//!
+//! - Successor: O(n)
+//! - Rank / unrank: O(n²)
+
+use std::ops::Range;
+
+use super::cursor::{seek_nth, Cursor, Seekable};
+use super::multiset::{multiset_count, unrank_multiset_perm};
+use super::ranking::{try_rank_perm, unrank_perm};
+use crate::arith::rank_int::RankError;
+
+/// Generate the next colexicographic permutation in-place.
+///
+/// # Returns
+///
+/// * `true` if the permutation was advanced to the next one
+/// * `false` if it was already the last one (it is then reset to the first)
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::colex::next_colex_perm;
+/// let mut v = vec![2, 1, 0];
+/// assert!(next_colex_perm(&mut v));
+/// assert_eq!(v, vec![1, 2, 0]);
+/// ```
+pub fn next_colex_perm<T: Ord>(v: &mut [T]) -> bool {
+    let Some(j) = (1..v.len()).find(|&j| v[j] < v[j - 1]) else {
+        v.reverse();
+        return false;
+    };
+    let k = (0..j).find(|&k| v[k] > v[j]).unwrap();
+    v.swap(j, k);
+    v[..j].reverse();
+    true
+}
+
+/// Lazy iterator over all distinct permutations of a multiset in colex order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::colex::ColexPermutations;
+/// let perms: Vec<Vec<usize>> = ColexPermutations::new(&[0, 1, 2]).collect();
+/// assert_eq!(perms[0], vec![2, 1, 0]);
+/// assert_eq!(perms[5], vec![0, 1, 2]);
+/// assert_eq!(ColexPermutations::new(&[0, 1, 1]).count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColexPermutations<T> {
+    items: Vec<T>,
+    cursor: Cursor,
+}
+
+impl<T: Ord + Clone> ColexPermutations<T> {
+    /// Create an iterator starting from the colex-smallest arrangement
+    /// (`items` sorted in decreasing order).
+    pub fn new(items: &[T]) -> Self {
+        let mut items = items.to_vec();
+        items.sort_by(|a, b| b.cmp(a));
+        let cursor = Cursor::new(multiset_count(&items));
+        ColexPermutations { items, cursor }
+    }
+
+    /// Iterate over the permutations of `items` whose colex ranks lie in
+    /// `ranks`, starting from an unranked permutation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ranks.start > ranks.end`, if `ranks.end` exceeds the number
+    /// of distinct arrangements, or if that number does not fit in `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::permutations::colex::ColexPermutations;
+    /// let shard: Vec<Vec<u8>> = ColexPermutations::range(&[1, 2, 3], 4..6).collect();
+    /// assert_eq!(shard, vec![vec![2, 1, 3], vec![1, 2, 3]]);
+    /// ```
+    pub fn range(items: &[T], ranks: Range<usize>) -> Self {
+        let start = ranks.start;
+        let mut it = ColexPermutations::new(items);
+        it.cursor = Cursor::range(multiset_count(items), ranks);
+        it.seek(start);
+        it
+    }
+
+    /// Advance and lend the next permutation without allocating.
+    pub fn next_slice(&mut self) -> Option<&[T]> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if !self.cursor.start() && !next_colex_perm(&mut self.items) {
+            self.cursor.finish();
+            return None;
+        }
+        self.cursor.advance();
+        Some(&self.items)
+    }
+}
+
+impl<T: Ord + Clone> Iterator for ColexPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        self.next_slice().map(<[T]>::to_vec)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    fn nth(&mut self, m: usize) -> Option<Vec<T>> {
+        seek_nth(self, m)
+    }
+}
+
+impl<T: Ord + Clone> Seekable for ColexPermutations<T> {
+    fn cursor(&mut self) -> &mut Cursor {
+        &mut self.cursor
+    }
+
+    fn seek(&mut self, rank: usize) {
+        self.cursor.seek(rank);
+        if !self.cursor.at_end() {
+            self.items = unrank_multiset_perm(rank, &self.items);
+            self.items.reverse();
+        }
+    }
+
+    fn step(&mut self) -> Option<()> {
+        self.next_slice().map(drop)
+    }
+}
+
+/// Colexicographic rank of a permutation of `0..n`.
+///
+/// # Panics
+///
+/// Panics if `p` is not a permutation of `0..n`, or if the rank does not fit
+/// in `usize` (possible for `n > 20`).
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::colex::rank_colex_perm;
+/// assert_eq!(rank_colex_perm(&[2, 1, 0]), 0);
+/// assert_eq!(rank_colex_perm(&[0, 1, 2]), 5);
+/// ```
+pub fn rank_colex_perm(p: &[usize]) -> usize {
+    let rev: Vec<usize> = p.iter().rev().copied().collect();
+    match try_rank_perm(&rev) {
+        Ok(rank) => rank,
+        Err(RankError::InvalidInput) => panic!("not a permutation of 0..{}", p.len()),
+        Err(e) => panic!("colex rank overflows usize: {e:?}"),
+    }
+}
+
+/// The permutation of `0..n` with the given colexicographic rank.
+pub fn unrank_colex_perm(rank: usize, n: usize) -> Vec<usize> {
+    let mut p = unrank_perm(rank, n);
+    p.reverse();
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colex_order_and_ranks() {
+        let perms: Vec<Vec<usize>> = ColexPermutations::new(&[0, 1, 2, 3, 4]).collect();
+        assert_eq!(perms.len(), 120);
+        for (r, p) in perms.iter().enumerate() {
+            assert_eq!(rank_colex_perm(p), r);
+            assert_eq!(unrank_colex_perm(r, 5), *p);
+        }
+        let rev_lex = |p: &Vec<usize>| p.iter().rev().copied().collect::<Vec<_>>();
+        assert!(perms.windows(2).all(|w| rev_lex(&w[0]) < rev_lex(&w[1])));
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn test_colex_rank_rejects_non_permutation() {
+        rank_colex_perm(&[0, 0]);
+    }
+
+    #[test]
+    fn test_colex_multiset() {
+        let perms: Vec<Vec<char>> = ColexPermutations::new(&['a', 'b', 'a']).collect();
+        assert_eq!(perms, vec![
+            vec!['b', 'a', 'a'],
+            vec!['a', 'b', 'a'],
+            vec!['a', 'a', 'b'],
+        ]);
+    }
+
+    #[test]
+    fn test_colex_size_hint_tracks_progress() {
+        let mut it = ColexPermutations::new(&[2, 1, 2, 1]);
+        assert_eq!(it.size_hint(), (6, Some(6)));
+        it.next_slice();
+        it.next();
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        assert_eq!(it.by_ref().count(), 4);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(ColexPermutations::<u8>::new(&[]).count(), 1);
+    }
+
+    #[test]
+    fn test_colex_seek_resumes_generation() {
+        let items = ['c', 'a', 'b', 'a', 'c'];
+        let all: Vec<Vec<char>> = ColexPermutations::new(&items).collect();
+        let total = all.len();
+        for start in 0..=total {
+            let shard: Vec<Vec<char>> =
+                ColexPermutations::range(&items, start..total).collect();
+            assert_eq!(shard, all[start..]);
+        }
+        let mut it = ColexPermutations::new(&items);
+        assert_eq!(it.nth(11), Some(all[11].clone()));
+        assert_eq!(it.next(), Some(all[12].clone()));
+    }
+}
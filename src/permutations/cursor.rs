@@ -0,0 +1,231 @@
+//! Position bookkeeping shared by the lazy permutation generators.
+//!
+//! Each generator visits a sequence whose items have ranks `0..total`. A
+//! [`Cursor`] records the rank of the next item to yield and one past the last
+//! rank to yield, so `size_hint`, `range` and `nth` need no per-type code:
+//! a generator implements [`Seekable`] and forwards [`Iterator::nth`] to
+//! [`seek_nth`]. When `total` does not fit in `usize` the end is unknown, and
+//! the generator reports exhaustion itself through [`Cursor::finish`].
+
+use std::ops::Range;
+
+/// How [`Iterator::nth`] should reach its target.
+enum Skip {
+    /// Reposition at this rank by unranking.
+    Seek(usize),
+    /// Step item by item; the end rank is unknown or no items are skipped.
+    Step,
+    /// The target lies past the end; the cursor is now exhausted.
+    End,
+}
+
+/// Rank range still to be visited by a generator.
+#[derive(Debug, Clone)]
+pub struct Cursor {
+    started: bool,
+    done: bool,
+    next: usize,
+    end: Option<usize>,
+}
+
+impl Cursor {
+    /// A cursor over all `total` items, or an unbounded one if `total` is `None`.
+    pub fn new(total: Option<usize>) -> Self {
+        Cursor { started: false, done: false, next: 0, end: total }
+    }
+
+    /// A cursor over the items whose ranks lie in `ranks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `total` is `None`, if `ranks.start > ranks.end`, or if
+    /// `ranks.end > total`.
+    pub fn range(total: Option<usize>, ranks: Range<usize>) -> Self {
+        let total = total.expect("number of permutations overflows usize");
+        assert!(
+            ranks.start <= ranks.end && ranks.end <= total,
+            "rank range {:?} out of bounds for {} permutations",
+            ranks,
+            total
+        );
+        Cursor { started: false, done: false, next: ranks.start, end: Some(ranks.end) }
+    }
+
+    /// Whether the next rank lies past the end.
+    pub fn at_end(&self) -> bool {
+        self.end == Some(self.next)
+    }
+
+    /// Whether no items remain.
+    pub fn exhausted(&self) -> bool {
+        self.done || self.at_end()
+    }
+
+    /// Mark the generator as started, returning `true` on the first call
+    /// after construction or a seek: the current item is then yielded as is.
+    pub fn start(&mut self) -> bool {
+        !std::mem::replace(&mut self.started, true)
+    }
+
+    /// Record that one item was yielded.
+    pub fn advance(&mut self) {
+        self.next = self.next.saturating_add(1);
+    }
+
+    /// Record that the generator ran past its last item.
+    pub fn finish(&mut self) {
+        self.done = true;
+    }
+
+    /// Make `rank` the next item to yield; the caller repositions its state.
+    pub fn seek(&mut self, rank: usize) {
+        self.next = rank;
+        self.started = false;
+    }
+
+    /// Decide how to skip `m` items before yielding the next one.
+    fn skip(&mut self, m: usize) -> Skip {
+        let Some(end) = self.end else {
+            return Skip::Step;
+        };
+        if self.done || m >= end - self.next {
+            self.next = end;
+            return Skip::End;
+        }
+        if m == 0 { Skip::Step } else { Skip::Seek(self.next + m) }
+    }
+
+    /// Bounds on the number of items left, for [`Iterator::size_hint`].
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match self.end {
+            Some(end) => (end - self.next, Some(end - self.next)),
+            // more than usize::MAX items in total, of which `next` are gone
+            None => (usize::MAX - self.next, None),
+        }
+    }
+}
+
+/// A generator driven by a [`Cursor`] that can reposition itself at any rank.
+pub trait Seekable: Iterator {
+    /// The cursor tracking the generator's position.
+    fn cursor(&mut self) -> &mut Cursor;
+
+    /// Position the generator so that the next item yielded has `rank`.
+    fn seek(&mut self, rank: usize);
+
+    /// Move past one item without returning it, or return `None` if none is
+    /// left.
+    fn step(&mut self) -> Option<()> {
+        self.next().map(drop)
+    }
+}
+
+/// [`Iterator::nth`] for a [`Seekable`] generator: jumps to the target rank
+/// with one [`Seekable::seek`] when the end rank is known, and steps item by
+/// item when the total overflows `usize`.
+pub fn seek_nth<G: Seekable>(g: &mut G, m: usize) -> Option<G::Item> {
+    match g.cursor().skip(m) {
+        Skip::Seek(rank) => g.seek(rank),
+        Skip::Step => {
+            for _ in 0..m {
+                g.step()?;
+            }
+        }
+        Skip::End => return None,
+    }
+    g.next()
+}
+
+/// `n!`, or `None` if it does not fit in `usize`.
+pub fn factorial(n: usize) -> Option<usize> {
+    (1..=n).try_fold(1usize, |f, i| f.checked_mul(i))
+}
+
+/// Whether `p` is a permutation of `0..p.len()`.
+pub fn is_permutation(p: &[usize]) -> bool {
+    let n = p.len();
+    let mut seen = vec![false; n];
+    p.iter().all(|&x| x < n && !std::mem::replace(&mut seen[x], true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Yields its ranks, counting how it got there.
+    struct Ranks {
+        cursor: Cursor,
+        value: usize,
+        seeks: usize,
+        steps: usize,
+    }
+
+    impl Ranks {
+        fn new(total: Option<usize>) -> Self {
+            Ranks { cursor: Cursor::new(total), value: 0, seeks: 0, steps: 0 }
+        }
+    }
+
+    impl Iterator for Ranks {
+        type Item = usize;
+
+        fn next(&mut self) -> Option<usize> {
+            if self.cursor.exhausted() {
+                return None;
+            }
+            if !self.cursor.start() {
+                self.value += 1;
+                self.steps += 1;
+            }
+            self.cursor.advance();
+            Some(self.value)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.cursor.size_hint()
+        }
+
+        fn nth(&mut self, m: usize) -> Option<usize> {
+            seek_nth(self, m)
+        }
+    }
+
+    impl Seekable for Ranks {
+        fn cursor(&mut self) -> &mut Cursor {
+            &mut self.cursor
+        }
+
+        fn seek(&mut self, rank: usize) {
+            self.cursor.seek(rank);
+            self.value = rank;
+            self.seeks += 1;
+        }
+    }
+
+    #[test]
+    fn test_seek_nth_jumps_when_the_end_is_known() {
+        let mut it = Ranks::new(Some(100));
+        assert_eq!(it.nth(0), Some(0));
+        assert_eq!(it.nth(40), Some(41));
+        assert_eq!(it.next(), Some(42));
+        assert_eq!((it.seeks, it.steps), (1, 1));
+        assert_eq!(it.size_hint(), (57, Some(57)));
+        assert_eq!(it.nth(56), Some(99));
+        assert_eq!(it.nth(0), None);
+        let mut it = Ranks::new(Some(100));
+        assert_eq!(it.nth(100), None);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_seek_nth_steps_when_the_total_overflows() {
+        let mut it = Ranks::new(None);
+        assert_eq!(it.nth(40), Some(40));
+        assert_eq!((it.seeks, it.steps), (0, 40));
+        assert_eq!(it.size_hint(), (usize::MAX - 41, None));
+    }
+}
@@ -0,0 +1,359 @@
+//! # Chapter 1: Permutations — Heap's Algorithm
+//!
+//! Generates all permutations of `0..n` so that each one differs from the
+//! previous by a single swap (not necessarily of adjacent positions).
+//!
+//! ## Algorithm Description
+//!
+//! - Keep a mixed-radix counter `c[1..n]` with `c[i] ∈ 0..=i`.
+//! - To advance, find the smallest `i` with `c[i] < i`, resetting the digits
+//!   passed over to 0. If none exists, all `n!` permutations have been seen.
+//! - Swap positions `(0, i)` if `i` is even, `(c[i], i)` if `i` is odd, then
+//!   increment `c[i]`.
+//!
+//! ## Ranking
+//!
+//! After `t` steps the counter holds the digits of `t` in the factorial
+//! number system, `t = Σ c[i] · i!`. Between two increments of `c[i]` the
+//! lower digits make one full pass, which permutes positions `0..i` in a way
+//! that does not depend on the values stored there. Precomputing that
+//! rearrangement for every `i` lets [`unrank_heap_perm`] replay `c[i]` passes
+//! per digit instead of `t` single swaps. [`rank_heap_perm`] runs the same
+//! replay backwards: position `i` is untouched by the lower passes, so `c[i]`
+//! is the number of passes needed before it holds `p[i]`. The same replay
+//! restores the counter, so [`HeapPermutations::range`] and
+//! [`Iterator::nth`] can resume generation from any rank.
+//! [`try_rank_heap_perm`] ranks in any [`RankInt`] type and reports overflow
+//! instead of wrapping.
+//!
+//! ## Complexity
+//!
+//! - Time: O(1) amortized per permutation
+//! - Rank / unrank: O(n³)
+//! - Space: O(n)
+
+use std::ops::Range;
+
+use super::cursor::{factorial, is_permutation, seek_nth, Cursor, Seekable};
+use crate::arith::rank_int::{RankError, RankInt};
+
+/// Lazy generator of permutations of `0..n` in Heap's order.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::heap::HeapPermutations;
+/// let perms: Vec<Vec<usize>> = HeapPermutations::new(3).collect();
+/// assert_eq!(perms, vec![
+///     vec![0, 1, 2], vec![1, 0, 2], vec![2, 0, 1],
+///     vec![0, 2, 1], vec![1, 2, 0], vec![2, 1, 0],
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeapPermutations {
+    perm: Vec<usize>,
+    counter: Vec<usize>,
+    cursor: Cursor,
+}
+
+impl HeapPermutations {
+    /// Start at the identity permutation of `0..n`.
+    pub fn new(n: usize) -> Self {
+        HeapPermutations {
+            perm: (0..n).collect(),
+            counter: vec![0; n],
+            cursor: Cursor::new(factorial(n)),
+        }
+    }
+
+    /// Iterate over the permutations of `0..n` whose ranks in Heap's order lie in
+    /// `ranks`, starting from an unranked permutation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ranks.start > ranks.end`, if `ranks.end > n!`, or if `n!`
+    /// does not fit in `usize`.
+    pub fn range(n: usize, ranks: Range<usize>) -> Self {
+        let start = ranks.start;
+        let mut it = HeapPermutations::new(n);
+        it.cursor = Cursor::range(factorial(n), ranks);
+        it.seek(start);
+        it
+    }
+
+    /// The current permutation.
+    pub fn current(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Advance by one swap.
+    ///
+    /// # Returns
+    ///
+    /// `Some((i, j))` with `i < j` if positions `i` and `j` were exchanged, or
+    /// `None` once all `n!` permutations have been visited.
+    pub fn next_swap(&mut self) -> Option<(usize, usize)> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if self.cursor.start() {
+            // the starting permutation counts as visited
+            self.cursor.advance();
+            if self.cursor.at_end() {
+                return None;
+            }
+        }
+        let n = self.perm.len();
+        let mut i = 1;
+        while i < n && self.counter[i] >= i {
+            self.counter[i] = 0;
+            i += 1;
+        }
+        if i >= n {
+            self.cursor.finish();
+            return None;
+        }
+        let j = heap_partner(i, self.counter[i]);
+        self.perm.swap(j, i);
+        self.counter[i] += 1;
+        self.cursor.advance();
+        Some((j, i))
+    }
+
+    /// Consume the generator, yielding only the swapped position pairs.
+    pub fn swaps(mut self) -> impl Iterator<Item = (usize, usize)> {
+        std::iter::from_fn(move || self.next_swap())
+    }
+}
+
+impl Iterator for HeapPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if self.cursor.start() {
+            self.cursor.advance();
+            return Some(self.perm.clone());
+        }
+        self.next_swap().map(|_| self.perm.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    fn nth(&mut self, m: usize) -> Option<Vec<usize>> {
+        seek_nth(self, m)
+    }
+}
+
+impl Seekable for HeapPermutations {
+    fn cursor(&mut self) -> &mut Cursor {
+        &mut self.cursor
+    }
+
+    fn seek(&mut self, rank: usize) {
+        self.cursor.seek(rank);
+        if !self.cursor.at_end() {
+            (self.perm, self.counter) = heap_state(rank, self.perm.len());
+        }
+    }
+}
+
+/// Rank of `p` in Heap's order, i.e. the number of swaps from the identity.
+///
+/// # Panics
+///
+/// Panics if `p` is not a permutation of `0..n`, or if the rank does not fit
+/// in `usize` (possible for `n > 20`); see [`try_rank_heap_perm`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::heap::rank_heap_perm;
+/// assert_eq!(rank_heap_perm(&[0, 1, 2]), 0);
+/// assert_eq!(rank_heap_perm(&[0, 2, 1]), 3);
+/// ```
+pub fn rank_heap_perm(p: &[usize]) -> usize {
+    match try_rank_heap_perm(p) {
+        Ok(rank) => rank,
+        Err(RankError::InvalidInput) => panic!("not a permutation of 0..{}", p.len()),
+        Err(e) => panic!("Heap rank overflows usize: {e:?}"),
+    }
+}
+
+/// Rank of `p` in Heap's order in any [`RankInt`] type.
+///
+/// # Returns
+///
+/// The rank, `Err(RankError::Overflow)` if it does not fit in `R`, or
+/// `Err(RankError::InvalidInput)` if `p` is not a permutation of `0..n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::arith::rank_int::RankError;
+/// use rusty_combinatorial::permutations::heap::try_rank_heap_perm;
+/// let p: Vec<usize> = (0..25).rev().collect();
+/// assert_eq!(try_rank_heap_perm::<u64>(&p), Err(RankError::Overflow));
+/// assert!(try_rank_heap_perm::<u128>(&p).is_ok());
+/// ```
+pub fn try_rank_heap_perm<R: RankInt>(p: &[usize]) -> Result<R, RankError> {
+    if !is_permutation(p) {
+        return Err(RankError::InvalidInput);
+    }
+    let n = p.len();
+    let passes = heap_passes(n);
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut rank = R::zero();
+    for i in (1..n).rev() {
+        let mut c = 0;
+        while perm[i] != p[i] {
+            heap_digit_step(&mut perm, &passes[i], i, c);
+            c += 1;
+        }
+        rank = rank
+            .checked_mul_usize(i + 1)
+            .and_then(|r| r.checked_add_usize(c))
+            .ok_or(RankError::Overflow)?;
+    }
+    Ok(rank)
+}
+
+/// The permutation of `0..n` reached after `rank` steps of Heap's algorithm.
+///
+/// # Panics
+///
+/// Panics if `rank >= n!`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::heap::unrank_heap_perm;
+/// assert_eq!(unrank_heap_perm(3, 3), vec![0, 2, 1]);
+/// ```
+pub fn unrank_heap_perm(rank: usize, n: usize) -> Vec<usize> {
+    heap_state(rank, n).0
+}
+
+/// Permutation and counter after `rank` steps.
+fn heap_state(mut rank: usize, n: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut counter = vec![0; n];
+    for (i, c) in counter.iter_mut().enumerate().skip(1) {
+        *c = rank % (i + 1);
+        rank /= i + 1;
+    }
+    assert!(rank == 0, "rank out of range for n = {}", n);
+    let passes = heap_passes(n);
+    let mut perm: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        for c in 0..counter[i] {
+            heap_digit_step(&mut perm, &passes[i], i, c);
+        }
+    }
+    (perm, counter)
+}
+
+/// Position swapped with `i` when digit `i` is incremented from `c`.
+fn heap_partner(i: usize, c: usize) -> usize {
+    if i.is_multiple_of(2) { 0 } else { c }
+}
+
+/// One full pass of the digits below `i`, then the increment of `c[i]` from `c`.
+fn heap_digit_step(perm: &mut [usize], pass: &[usize], i: usize, c: usize) {
+    apply_pass(perm, pass);
+    perm.swap(heap_partner(i, c), i);
+}
+
+/// Rearrange the prefix of `perm` covered by `pass`.
+fn apply_pass(perm: &mut [usize], pass: &[usize]) {
+    let before = perm[..pass.len()].to_vec();
+    for (x, &from) in pass.iter().enumerate() {
+        perm[x] = before[from];
+    }
+}
+
+/// `passes[i][x]` is the position whose entry lands on `x` after a full pass
+/// of the digits below `i` (the `i! - 1` swaps that permute positions `0..i`).
+fn heap_passes(n: usize) -> Vec<Vec<usize>> {
+    let mut passes: Vec<Vec<usize>> = vec![Vec::new(), vec![0]];
+    for i in 1..n.saturating_sub(1) {
+        let mut perm: Vec<usize> = (0..=i).collect();
+        for c in 0..i {
+            heap_digit_step(&mut perm, &passes[i], i, c);
+        }
+        apply_pass(&mut perm, &passes[i]);
+        passes.push(perm);
+    }
+    passes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_heap_visits_all_with_single_swaps() {
+        for n in 0..=6 {
+            let perms: Vec<Vec<usize>> = HeapPermutations::new(n).collect();
+            let distinct: HashSet<&Vec<usize>> = perms.iter().collect();
+            assert_eq!(distinct.len(), (1..=n).product::<usize>());
+            for w in perms.windows(2) {
+                assert_eq!(w[0].iter().zip(&w[1]).filter(|(a, b)| a != b).count(), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_heap_rank_unrank() {
+        for n in 0..=6 {
+            for (r, p) in HeapPermutations::new(n).enumerate() {
+                assert_eq!(rank_heap_perm(&p), r);
+                assert_eq!(unrank_heap_perm(r, n), p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_heap_try_rank_checks_overflow() {
+        assert_eq!(try_rank_heap_perm::<usize>(&[1, 1]), Err(RankError::InvalidInput));
+        let p: Vec<usize> = (0..21).rev().collect();
+        let wide: u128 = try_rank_heap_perm(&p).unwrap();
+        assert!(wide > u64::MAX as u128 && wide < (1..=21u128).product());
+        assert_eq!(try_rank_heap_perm::<u64>(&p), Err(RankError::Overflow));
+        for (r, p) in HeapPermutations::new(5).enumerate() {
+            assert_eq!(try_rank_heap_perm::<u128>(&p), Ok(r as u128));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn test_heap_rank_rejects_non_permutation() {
+        rank_heap_perm(&[1, 1]);
+    }
+
+    #[test]
+    fn test_heap_swaps_replay() {
+        let mut perm: Vec<usize> = (0..5).collect();
+        let all: Vec<Vec<usize>> = HeapPermutations::new(5).collect();
+        for (step, (i, j)) in HeapPermutations::new(5).swaps().enumerate() {
+            perm.swap(i, j);
+            assert_eq!(perm, all[step + 1]);
+        }
+    }
+
+    #[test]
+    fn test_heap_seek_resumes_generation() {
+        let all: Vec<Vec<usize>> = HeapPermutations::new(5).collect();
+        for start in (0..=120).step_by(7) {
+            let shard: Vec<Vec<usize>> = HeapPermutations::range(5, start..120).collect();
+            assert_eq!(shard, all[start..]);
+        }
+        let mut it = HeapPermutations::new(5);
+        assert_eq!(it.nth(50), Some(all[50].clone()));
+        assert_eq!(it.next(), Some(all[51].clone()));
+    }
+}
@@ -18,6 +18,19 @@
 //!
 //! - Time: O(n)
 //! - Space: O(1) (in-place mutation)
+//!
+//! ## Lazy Iteration
+//!
+//! [`LexPermutations`] wraps `next_lex_perm` in an iterator. The input is sorted
+//! once up front, so every distinct arrangement is visited exactly once even when
+//! the input contains duplicates (multiset permutations).
+//! [`LexPermutations::range`] and [`Iterator::nth`] jump to any rank with
+//! [`unrank_multiset_perm`], so an enumeration can be split into shards.
+
+use std::ops::Range;
+
+use super::cursor::{seek_nth, Cursor, Seekable};
+use super::multiset::{multiset_count, unrank_multiset_perm};
 
 /// Generate the next lexicographic permutation in-place.
 ///
@@ -64,6 +77,129 @@ pub fn next_lex_perm<T: Ord>(v: &mut [T]) -> bool {
     true
 }
 
+/// Lazy iterator over all distinct permutations of a multiset in lex order.
+///
+/// Yields owned `Vec<T>` values through [`Iterator`], or borrowed slices through
+/// the streaming [`LexPermutations::next_slice`], which performs no allocation
+/// per step.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::lex::LexPermutations;
+/// let perms: Vec<Vec<char>> = LexPermutations::new(&['b', 'a', 'a']).collect();
+/// assert_eq!(perms, vec![
+///     vec!['a', 'a', 'b'],
+///     vec!['a', 'b', 'a'],
+///     vec!['b', 'a', 'a'],
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LexPermutations<T> {
+    items: Vec<T>,
+    cursor: Cursor,
+}
+
+impl<T: Ord + Clone> LexPermutations<T> {
+    /// Create an iterator over the permutations of `items`.
+    ///
+    /// The items are copied and sorted, so iteration always starts from the
+    /// lexicographically smallest arrangement.
+    pub fn new(items: &[T]) -> Self {
+        let mut items = items.to_vec();
+        items.sort();
+        let cursor = Cursor::new(multiset_count(&items));
+        LexPermutations { items, cursor }
+    }
+
+    /// Iterate over the permutations of `items` whose lex ranks lie in `ranks`.
+    ///
+    /// The first permutation is unranked directly rather than reached by
+    /// stepping, so disjoint ranges can be handed to different workers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ranks.start > ranks.end`, if `ranks.end` exceeds the number
+    /// of distinct arrangements, or if that number does not fit in `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::permutations::lex::LexPermutations;
+    /// let shard: Vec<Vec<u8>> = LexPermutations::range(&[1, 2, 3], 2..4).collect();
+    /// assert_eq!(shard, vec![vec![2, 1, 3], vec![2, 3, 1]]);
+    /// ```
+    pub fn range(items: &[T], ranks: Range<usize>) -> Self {
+        let start = ranks.start;
+        let mut it = LexPermutations::new(items);
+        it.cursor = Cursor::range(multiset_count(items), ranks);
+        it.seek(start);
+        it
+    }
+
+    /// Advance to the next permutation and lend it as a slice.
+    ///
+    /// This is the streaming counterpart of [`Iterator::next`]: the returned
+    /// slice borrows the iterator's internal buffer, so no allocation happens.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::permutations::lex::LexPermutations;
+    /// let mut it = LexPermutations::new(&[1, 2, 3]);
+    /// let mut count = 0;
+    /// while let Some(p) = it.next_slice() {
+    ///     assert_eq!(p.len(), 3);
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 6);
+    /// ```
+    pub fn next_slice(&mut self) -> Option<&[T]> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if !self.cursor.start() && !next_lex_perm(&mut self.items) {
+            self.cursor.finish();
+            return None;
+        }
+        self.cursor.advance();
+        Some(&self.items)
+    }
+}
+
+impl<T: Ord + Clone> Iterator for LexPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        self.next_slice().map(<[T]>::to_vec)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    fn nth(&mut self, m: usize) -> Option<Vec<T>> {
+        seek_nth(self, m)
+    }
+}
+
+impl<T: Ord + Clone> Seekable for LexPermutations<T> {
+    fn cursor(&mut self) -> &mut Cursor {
+        &mut self.cursor
+    }
+
+    fn seek(&mut self, rank: usize) {
+        self.cursor.seek(rank);
+        if !self.cursor.at_end() {
+            self.items = unrank_multiset_perm(rank, &self.items);
+        }
+    }
+
+    fn step(&mut self) -> Option<()> {
+        self.next_slice().map(drop)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,4 +217,41 @@ mod tests {
         assert!(!next_lex_perm(&mut v));
         assert_eq!(v, vec![1, 2, 3]); // wrapped to first
     }
+
+    #[test]
+    fn test_lex_permutations_multiset() {
+        let it = LexPermutations::new(&[2, 1, 2, 1]);
+        assert_eq!(it.size_hint(), (6, Some(6)));
+        let perms: Vec<Vec<i32>> = it.collect();
+        assert_eq!(perms.len(), 6);
+        assert_eq!(perms[0], vec![1, 1, 2, 2]);
+        assert_eq!(perms[5], vec![2, 2, 1, 1]);
+        assert!(perms.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_lex_permutations_size_hint_tracks_progress() {
+        let mut it = LexPermutations::new(&[0, 1, 2, 3]);
+        assert_eq!(it.size_hint(), (24, Some(24)));
+        it.next_slice();
+        it.next();
+        assert_eq!(it.size_hint(), (22, Some(22)));
+        assert_eq!(it.count(), 22);
+        let empty: Vec<Vec<u8>> = LexPermutations::new(&[]).collect();
+        assert_eq!(empty, vec![Vec::<u8>::new()]);
+    }
+
+    #[test]
+    fn test_lex_permutations_seek_resumes_generation() {
+        let items = [3, 1, 2, 1, 3];
+        let all: Vec<Vec<i32>> = LexPermutations::new(&items).collect();
+        let total = all.len();
+        for start in 0..=total {
+            let shard: Vec<Vec<i32>> = LexPermutations::range(&items, start..total).collect();
+            assert_eq!(shard, all[start..]);
+        }
+        let mut it = LexPermutations::new(&items);
+        assert_eq!(it.nth(7), Some(all[7].clone()));
+        assert_eq!(it.next(), Some(all[8].clone()));
+    }
 }
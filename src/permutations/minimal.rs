@@ -15,6 +15,32 @@
 //!
 //! - Time: O(n!) total, O(1) amortized per permutation
 //! - Space: O(n)
+//!
+//! ## Streaming Generation (Even's Speedup)
+//!
+//! [`SjtPermutations`] produces the same sequence lazily. Instead of scanning for
+//! the largest mobile element, it treats the sequence of moves as a reflected
+//! mixed-radix Gray code (element `e` sweeps through `e + 1` positions) and
+//! drives it with focus pointers, so every step is loopless: O(1) worst case.
+//! It can report just the transposition index of each step, letting callers
+//! update incremental cost functions without rebuilding the permutation.
+//!
+//! ## Ranking
+//!
+//! Element `e` sits `g` places left of its rightmost slot among `0..=e`,
+//! where `g` is its digit in the Gray code. [`rank_sjt_perm`] reads these
+//! digits off a permutation and converts them to the plain mixed-radix rank
+//! (a digit runs backwards whenever the higher digits sum to an odd sweep
+//! count); [`unrank_sjt_perm`] reverses the conversion and inserts each
+//! element at its slot. Both are O(n²), which lets
+//! [`SjtPermutations::range`] and [`Iterator::nth`] start anywhere.
+//! [`try_rank_sjt_perm`] ranks in any [`RankInt`] type and reports overflow
+//! instead of wrapping.
+
+use std::ops::Range;
+
+use super::cursor::{factorial, is_permutation, seek_nth, Cursor, Seekable};
+use crate::arith::rank_int::{RankError, RankInt};
 
 /// Generate all permutations of `n` elements in minimal-change (Gray‐code) order.
 ///
@@ -34,7 +60,6 @@
 /// assert_eq!(perms.len(), 6);
 /// assert_eq!(perms[0], vec![0, 1, 2]);
 /// ```
-
 pub fn sjt_permutations(n: usize) -> Vec<Vec<usize>> {
     #[derive(Clone)]
     struct Elem {
@@ -101,3 +126,366 @@ pub fn sjt_permutations(n: usize) -> Vec<Vec<usize>> {
 
     out
 }
+
+/// Lazy Steinhaus–Johnson–Trotter generator over permutations of `0..n`.
+///
+/// Iterating yields each permutation as an owned `Vec<usize>`, in the same order
+/// as [`sjt_permutations`]. For incremental consumers, [`SjtPermutations::next_swap`]
+/// advances one step and returns only the index `i` of the adjacent
+/// transposition `(i, i + 1)` that was applied.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::minimal::SjtPermutations;
+/// let mut it = SjtPermutations::new(3);
+/// assert_eq!(it.current(), &[0, 1, 2]);
+/// assert_eq!(it.next_swap(), Some(1));
+/// assert_eq!(it.current(), &[0, 2, 1]);
+/// let swaps: Vec<usize> = it.swaps().collect();
+/// assert_eq!(swaps, vec![0, 1, 0, 1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SjtPermutations {
+    perm: Vec<usize>,
+    pos: Vec<usize>,
+    // Reflected mixed-radix Gray code over the elements n-1, n-2, …, 1:
+    // digit d moves element n-1-d and has radix n-d.
+    digit: Vec<usize>,
+    forward: Vec<bool>,
+    focus: Vec<usize>,
+    cursor: Cursor,
+}
+
+impl SjtPermutations {
+    /// Start at the identity permutation of `0..n`.
+    pub fn new(n: usize) -> Self {
+        let digits = n.saturating_sub(1);
+        SjtPermutations {
+            perm: (0..n).collect(),
+            pos: (0..n).collect(),
+            digit: vec![0; digits],
+            forward: vec![true; digits],
+            focus: (0..=digits).collect(),
+            cursor: Cursor::new(factorial(n)),
+        }
+    }
+
+    /// Iterate over the permutations of `0..n` whose SJT ranks lie in `ranks`.
+    ///
+    /// The generator starts at the permutation of rank `ranks.start`, with its
+    /// Gray-code digits, directions and focus pointers rebuilt from the rank.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ranks.start > ranks.end`, if `ranks.end > n!`, or if `n!`
+    /// does not fit in `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::permutations::minimal::SjtPermutations;
+    /// let mut it = SjtPermutations::range(3, 2..5);
+    /// assert_eq!(it.current(), &[2, 0, 1]);
+    /// assert_eq!(it.swaps().collect::<Vec<_>>(), vec![1, 0]);
+    /// ```
+    pub fn range(n: usize, ranks: Range<usize>) -> Self {
+        let start = ranks.start;
+        let mut it = SjtPermutations::new(n);
+        it.cursor = Cursor::range(factorial(n), ranks);
+        it.seek(start);
+        it
+    }
+
+    /// The current permutation.
+    pub fn current(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Advance by one adjacent transposition.
+    ///
+    /// # Returns
+    ///
+    /// `Some(i)` if positions `i` and `i + 1` were swapped, or `None` once all
+    /// `n!` permutations have been visited. The starting permutation counts as
+    /// visited, so the first call moves to the second permutation.
+    pub fn next_swap(&mut self) -> Option<usize> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if self.cursor.start() {
+            // the starting permutation counts as visited
+            self.cursor.advance();
+            if self.cursor.at_end() {
+                return None;
+            }
+        }
+        let digits = self.digit.len();
+        let d = self.focus[0];
+        self.focus[0] = 0;
+        if d == digits {
+            self.cursor.finish();
+            return None;
+        }
+
+        // Move element e one step: left while its digit counts up, right otherwise.
+        let e = self.digit.len() - d;
+        let p = self.pos[e];
+        let i = if self.forward[d] { p - 1 } else { p };
+        self.perm.swap(i, i + 1);
+        self.pos[self.perm[i]] = i;
+        self.pos[self.perm[i + 1]] = i + 1;
+
+        if self.forward[d] {
+            self.digit[d] += 1;
+        } else {
+            self.digit[d] -= 1;
+        }
+        if self.digit[d] == 0 || self.digit[d] == e {
+            self.forward[d] = !self.forward[d];
+            self.focus[d] = self.focus[d + 1];
+            self.focus[d + 1] = d + 1;
+        }
+        self.cursor.advance();
+        Some(i)
+    }
+
+    /// Consume the generator, yielding only the transposition indices.
+    ///
+    /// Produces `n! - 1` indices: one per step after the identity.
+    pub fn swaps(mut self) -> impl Iterator<Item = usize> {
+        std::iter::from_fn(move || self.next_swap())
+    }
+}
+
+impl Iterator for SjtPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if self.cursor.start() {
+            self.cursor.advance();
+            return Some(self.perm.clone());
+        }
+        self.next_swap().map(|_| self.perm.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    fn nth(&mut self, m: usize) -> Option<Vec<usize>> {
+        seek_nth(self, m)
+    }
+}
+
+impl Seekable for SjtPermutations {
+    fn cursor(&mut self) -> &mut Cursor {
+        &mut self.cursor
+    }
+
+    fn seek(&mut self, rank: usize) {
+        self.cursor.seek(rank);
+        if self.cursor.at_end() {
+            return;
+        }
+        let n = self.perm.len();
+        let digits = self.digit.len();
+        let mut sweeps = rank;
+        let mut last = vec![false; digits];
+        for (d, (g, forward)) in self.digit.iter_mut().zip(&mut self.forward).enumerate() {
+            let radix = n - d;
+            let a = sweeps % radix;
+            sweeps /= radix;
+            last[d] = a == radix - 1;
+            *g = if sweeps.is_multiple_of(2) { a } else { radix - 1 - a };
+            // the direction flips as soon as a sweep reaches its end
+            *forward = (sweeps + last[d] as usize).is_multiple_of(2);
+        }
+        // focus[j] is the next digit to move among j, j+1, …, unless digit
+        // j - 1 has finished its sweep, in which case it is j itself
+        self.focus[digits] = digits;
+        for j in (0..digits).rev() {
+            self.focus[j] = if last[j] { self.focus[j + 1] } else { j };
+        }
+        for j in 1..=digits {
+            if last[j - 1] {
+                self.focus[j] = j;
+            }
+        }
+        self.perm = sjt_from_digits(&self.digit, n);
+        for (i, &x) in self.perm.iter().enumerate() {
+            self.pos[x] = i;
+        }
+    }
+}
+
+/// Rank of `p` in Steinhaus–Johnson–Trotter order.
+///
+/// # Panics
+///
+/// Panics if `p` is not a permutation of `0..n`, or if the rank does not fit
+/// in `usize` (possible for `n > 20`); see [`try_rank_sjt_perm`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::minimal::rank_sjt_perm;
+/// assert_eq!(rank_sjt_perm(&[0, 1, 2]), 0);
+/// assert_eq!(rank_sjt_perm(&[1, 0, 2]), 5);
+/// ```
+pub fn rank_sjt_perm(p: &[usize]) -> usize {
+    match try_rank_sjt_perm(p) {
+        Ok(rank) => rank,
+        Err(RankError::InvalidInput) => panic!("not a permutation of 0..{}", p.len()),
+        Err(e) => panic!("SJT rank overflows usize: {e:?}"),
+    }
+}
+
+/// Rank of `p` in Steinhaus–Johnson–Trotter order in any [`RankInt`] type.
+///
+/// # Returns
+///
+/// The rank, `Err(RankError::Overflow)` if it does not fit in `R`, or
+/// `Err(RankError::InvalidInput)` if `p` is not a permutation of `0..n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::arith::rank_int::RankError;
+/// use rusty_combinatorial::permutations::minimal::try_rank_sjt_perm;
+/// let p: Vec<usize> = (0..25).rev().collect();
+/// assert_eq!(try_rank_sjt_perm::<u64>(&p), Err(RankError::Overflow));
+/// assert!(try_rank_sjt_perm::<u128>(&p).is_ok());
+/// ```
+pub fn try_rank_sjt_perm<R: RankInt>(p: &[usize]) -> Result<R, RankError> {
+    if !is_permutation(p) {
+        return Err(RankError::InvalidInput);
+    }
+    let mut rank = R::zero();
+    for e in 1..p.len() {
+        let radix = e + 1;
+        let slot = p.iter().filter(|&&x| x <= e).position(|&x| x == e).unwrap();
+        let g = e - slot;
+        let a = if rank.div_rem_usize(2).1 == 0 { g } else { radix - 1 - g };
+        rank = rank
+            .checked_mul_usize(radix)
+            .and_then(|r| r.checked_add_usize(a))
+            .ok_or(RankError::Overflow)?;
+    }
+    Ok(rank)
+}
+
+/// The permutation of `0..n` with the given rank in Steinhaus–Johnson–Trotter
+/// order.
+///
+/// # Panics
+///
+/// Panics if `rank >= n!`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::minimal::unrank_sjt_perm;
+/// assert_eq!(unrank_sjt_perm(5, 3), vec![1, 0, 2]);
+/// ```
+pub fn unrank_sjt_perm(rank: usize, n: usize) -> Vec<usize> {
+    assert!(factorial(n).is_none_or(|total| rank < total), "rank out of range");
+    let mut sweeps = rank;
+    let digit: Vec<usize> = (0..n.saturating_sub(1))
+        .map(|d| {
+            let radix = n - d;
+            let a = sweeps % radix;
+            sweeps /= radix;
+            if sweeps.is_multiple_of(2) { a } else { radix - 1 - a }
+        })
+        .collect();
+    sjt_from_digits(&digit, n)
+}
+
+/// Place each element `e` `digit[n-1-e]` slots left of the end of `0..=e`.
+fn sjt_from_digits(digit: &[usize], n: usize) -> Vec<usize> {
+    let mut perm = Vec::with_capacity(n);
+    for e in 0..n {
+        let g = if e == 0 { 0 } else { digit[n - 1 - e] };
+        perm.insert(e - g, e);
+    }
+    perm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sjt_iterator_matches_eager() {
+        for n in 0..=6 {
+            let lazy: Vec<Vec<usize>> = SjtPermutations::new(n).collect();
+            assert_eq!(lazy, sjt_permutations(n));
+        }
+    }
+
+    #[test]
+    fn test_sjt_swaps_replay() {
+        let n = 5;
+        let mut perm: Vec<usize> = (0..n).collect();
+        let eager = sjt_permutations(n);
+        let mut count = 1;
+        for (step, i) in SjtPermutations::new(n).swaps().enumerate() {
+            perm.swap(i, i + 1);
+            assert_eq!(perm, eager[step + 1]);
+            count += 1;
+        }
+        assert_eq!(count, 120);
+    }
+
+    #[test]
+    fn test_sjt_rank_unrank() {
+        for n in 0..=6 {
+            for (r, p) in sjt_permutations(n).iter().enumerate() {
+                assert_eq!(rank_sjt_perm(p), r);
+                assert_eq!(unrank_sjt_perm(r, n), *p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sjt_try_rank_checks_overflow() {
+        let p: Vec<usize> = (0..21).rev().collect();
+        let wide: u128 = try_rank_sjt_perm(&p).unwrap();
+        assert!(wide > u64::MAX as u128 && wide < (1..=21u128).product());
+        assert_eq!(try_rank_sjt_perm::<u64>(&p), Err(RankError::Overflow));
+        for (r, p) in sjt_permutations(5).iter().enumerate() {
+            assert_eq!(try_rank_sjt_perm::<u128>(p), Ok(r as u128));
+        }
+        // every usize rank is in range once n! overflows
+        let p = unrank_sjt_perm(usize::MAX, 21);
+        assert_eq!(rank_sjt_perm(&p), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "rank out of range")]
+    fn test_sjt_unrank_rejects_large_rank() {
+        unrank_sjt_perm(6, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn test_sjt_rank_rejects_non_permutation() {
+        rank_sjt_perm(&[5]);
+    }
+
+    #[test]
+    fn test_sjt_seek_resumes_generation() {
+        let all = sjt_permutations(6);
+        for start in 0..=720 {
+            let shard: Vec<Vec<usize>> = SjtPermutations::range(6, start..720).collect();
+            assert_eq!(shard, all[start..]);
+        }
+        let mut it = SjtPermutations::new(6);
+        assert_eq!(it.nth(250), Some(all[250].clone()));
+        assert_eq!(it.next(), Some(all[251].clone()));
+    }
+}
@@ -1,4 +1,13 @@
+pub mod colex;
+mod cursor;
+pub mod heap;
 pub mod lex;
 pub mod minimal;
+pub mod multiset;
+pub mod pattern;
+pub mod permutation;
 pub mod random;
-pub mod ranking;
\ No newline at end of file
+pub mod random_cycles;
+pub mod ranking;
+pub mod star;
+pub mod stats;
\ No newline at end of file
@@ -0,0 +1,200 @@
+//! # Chapter 1: Permutations — Multiset Ranking & Unranking
+//!
+//! Index the distinct arrangements of a multiset (e.g. `"AABBC"`) in
+//! lexicographic order — the order in which [`next_lex_perm`] visits them when
+//! started from the sorted multiset.
+//!
+//! ## Algorithm Description
+//!
+//! With `r` items left, `M` distinct arrangements of them, and `c_v` copies of
+//! value `v`, exactly `M · c_v / r` arrangements start with `v`.
+//!
+//! **Ranking**: at each position add `M · S / r`, where `S` counts the
+//!   remaining items smaller than the current one, then descend into the block
+//!   of the current item.
+//! **Unranking**: walk the distinct values in order, subtracting block sizes
+//!   until the rank falls inside a block.
+//!
+//! Each product `M · a / r` is evaluated as `(M / (r/g)) · (a/g)` with
+//! `g = gcd(a, r)`, which is exact and never exceeds the final value, so
+//! overflow is reported only when the true count does not fit.
+//!
+//! ## Complexity
+//!
+//! - Time: O(n · d) for `d` distinct values
+//! - Space: O(d)
+//!
+//! [`next_lex_perm`]: super::lex::next_lex_perm
+
+use crate::arith::rank_int::{RankError, RankInt};
+
+/// Number of distinct arrangements of `items`, `n! / ∏ mᵢ!`, in `usize`.
+///
+/// # Returns
+///
+/// `None` if the count does not fit in `usize`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::multiset::multiset_count;
+/// assert_eq!(multiset_count(b"AABBC"), Some(30));
+/// ```
+pub fn multiset_count<T: Ord>(items: &[T]) -> Option<usize> {
+    try_multiset_count(items).ok()
+}
+
+/// Number of distinct arrangements of `items` in any [`RankInt`] type.
+pub fn try_multiset_count<T: Ord, R: RankInt>(items: &[T]) -> Result<R, RankError> {
+    let mut count = R::from_usize(1).ok_or(RankError::Overflow)?;
+    let mut len = 0;
+    for (_, m) in value_counts(items) {
+        // multiply by C(len + m, m) one factor (len + run) / run at a time
+        for run in 1..=m {
+            len += 1;
+            count = mul_div(&count, len, run)?;
+        }
+    }
+    Ok(count)
+}
+
+/// Lexicographic rank of `p` among the arrangements of its own multiset.
+///
+/// # Panics
+///
+/// Panics if the rank does not fit in `usize`; see [`try_rank_multiset_perm`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::multiset::rank_multiset_perm;
+/// assert_eq!(rank_multiset_perm(b"AABBC"), 0);
+/// assert_eq!(rank_multiset_perm(b"AABCB"), 1);
+/// assert_eq!(rank_multiset_perm(b"CBBAA"), 29);
+/// ```
+pub fn rank_multiset_perm<T: Ord>(p: &[T]) -> usize {
+    try_rank_multiset_perm(p).expect("multiset permutation rank overflows usize")
+}
+
+/// Lexicographic rank of `p` among the arrangements of its own multiset, in
+/// any [`RankInt`] type.
+pub fn try_rank_multiset_perm<T: Ord, R: RankInt>(p: &[T]) -> Result<R, RankError> {
+    let mut counts = value_counts(p);
+    let mut total: R = try_multiset_count(p)?;
+    let mut rank = R::zero();
+    for (i, x) in p.iter().enumerate() {
+        let r = p.len() - i;
+        let k = counts.partition_point(|(v, _)| *v < x);
+        let smaller: usize = counts[..k].iter().map(|&(_, c)| c).sum();
+        let skipped = mul_div(&total, smaller, r)?;
+        rank = rank.checked_add(&skipped).ok_or(RankError::Overflow)?;
+        total = mul_div(&total, counts[k].1, r)?;
+        counts[k].1 -= 1;
+    }
+    Ok(rank)
+}
+
+/// The arrangement of `items` with the given lexicographic rank.
+///
+/// # Panics
+///
+/// Panics if `rank` is out of range; see [`try_unrank_multiset_perm`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::multiset::unrank_multiset_perm;
+/// assert_eq!(unrank_multiset_perm(1, b"BACAB"), b"AABCB".to_vec());
+/// ```
+pub fn unrank_multiset_perm<T: Ord + Clone>(rank: usize, items: &[T]) -> Vec<T> {
+    try_unrank_multiset_perm(&rank, items).expect("rank out of range")
+}
+
+/// The arrangement of `items` with the given [`RankInt`] rank.
+///
+/// # Returns
+///
+/// `Err(RankError::OutOfRange)` if `rank` is not below the number of
+/// arrangements.
+pub fn try_unrank_multiset_perm<T: Ord + Clone, R: RankInt>(
+    rank: &R,
+    items: &[T],
+) -> Result<Vec<T>, RankError> {
+    let mut counts = value_counts(items);
+    let mut total: R = try_multiset_count(items)?;
+    if *rank >= total {
+        return Err(RankError::OutOfRange);
+    }
+    let mut rank = rank.clone();
+    let mut out = Vec::with_capacity(items.len());
+    for i in 0..items.len() {
+        let r = items.len() - i;
+        for entry in counts.iter_mut().filter(|(_, c)| *c > 0) {
+            let block = mul_div(&total, entry.1, r)?;
+            if rank < block {
+                out.push(entry.0.clone());
+                entry.1 -= 1;
+                total = block;
+                break;
+            }
+            rank = rank.checked_sub(&block).ok_or(RankError::OutOfRange)?;
+        }
+    }
+    Ok(out)
+}
+
+/// Distinct values of `items` in increasing order with their multiplicities.
+fn value_counts<T: Ord>(items: &[T]) -> Vec<(&T, usize)> {
+    let mut sorted: Vec<&T> = items.iter().collect();
+    sorted.sort();
+    let mut counts: Vec<(&T, usize)> = Vec::new();
+    for x in sorted {
+        match counts.last_mut() {
+            Some((v, c)) if *v == x => *c += 1,
+            _ => counts.push((x, 1)),
+        }
+    }
+    counts
+}
+
+/// Exact `m · a / r`, given that `r` divides `m · a`.
+fn mul_div<R: RankInt>(m: &R, a: usize, r: usize) -> Result<R, RankError> {
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+    let g = gcd(a, r);
+    let (q, _) = m.div_rem_usize(r / g);
+    q.checked_mul_usize(a / g).ok_or(RankError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutations::lex::LexPermutations;
+
+    #[test]
+    fn test_rank_matches_lex_order() {
+        for items in [vec![0, 0, 1, 1, 2], vec![3, 1, 3, 1, 3, 2], vec![5, 4, 3, 2]] {
+            let mut count = 0;
+            for (r, p) in LexPermutations::new(&items).enumerate() {
+                assert_eq!(rank_multiset_perm(&p), r);
+                assert_eq!(unrank_multiset_perm(r, &items), p);
+                count += 1;
+            }
+            assert_eq!(multiset_count(&items), Some(count));
+            assert_eq!(try_unrank_multiset_perm(&count, &items), Err(RankError::OutOfRange));
+        }
+    }
+
+    #[test]
+    fn test_big_multiset_rank() {
+        use crate::arith::biguint::BigUint;
+        // 40 letters, 10 each of four symbols: 40! / (10!)^4 ≈ 4.7e21 > u64
+        let items: Vec<u8> = (0..40).map(|i| b"ACGT"[i % 4]).collect();
+        assert_eq!(try_multiset_count::<u8, u64>(&items), Err(RankError::Overflow));
+        let total: u128 = try_multiset_count(&items).unwrap();
+        assert_eq!(total, 4_705_360_871_073_570_227_520);
+        let r: BigUint = try_rank_multiset_perm(&items).unwrap();
+        assert_eq!(try_unrank_multiset_perm(&r, &items), Ok(items.clone()));
+    }
+}
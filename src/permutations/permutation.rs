@@ -0,0 +1,410 @@
+//! # Chapter 1: Permutations — The Permutation Type
+//!
+//! A validated permutation of `0..n` in one-line notation (`p[i]` is the image
+//! of `i`), with the symmetric-group operations and conversions between
+//! one-line notation, cycle notation and the Lehmer code.
+//!
+//! ## Algorithm Description
+//!
+//! - **Composition**: `(p ∘ q)(i) = p(q(i))`, i.e. apply `q` first.
+//! - **Inverse**: `p⁻¹[p[i]] = i`.
+//! - **Power**: binary exponentiation; negative exponents use the inverse.
+//! - **Cycles**: follow `i → p[i]` from each unvisited element.
+//! - **Order**: lcm of the cycle lengths.
+//! - **Sign**: `(-1)^(n − #cycles)`.
+//!
+//! ## Complexity
+//!
+//! - Composition, inverse, cycles, sign, order: O(n)
+//! - Power: O(n log |e|)
+//! - Lehmer code conversions: O(n²)
+
+use std::fmt;
+use std::ops::{Index, Mul};
+use std::str::FromStr;
+
+use super::ranking::{from_lehmer_code, lehmer_code};
+
+/// Errors produced when constructing a [`Permutation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermutationError {
+    /// The values are not a rearrangement of `0..n`.
+    NotAPermutation,
+    /// Some Lehmer digit `code[i]` is not in `0..n-i`.
+    InvalidLehmerCode,
+    /// The cycle notation is malformed or its cycles are not disjoint.
+    InvalidCycleNotation,
+}
+
+impl fmt::Display for PermutationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PermutationError::NotAPermutation => f.write_str("values are not a permutation of 0..n"),
+            PermutationError::InvalidLehmerCode => f.write_str("invalid Lehmer code"),
+            PermutationError::InvalidCycleNotation => f.write_str("invalid cycle notation"),
+        }
+    }
+}
+
+impl std::error::Error for PermutationError {}
+
+/// A permutation of `0..n` in one-line notation.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::permutation::Permutation;
+/// let p: Permutation = "(0 2 1)(3 4)".parse().unwrap();
+/// assert_eq!(p.as_slice(), &[2, 0, 1, 4, 3]);
+/// assert_eq!(p.order(), Some(6));
+/// assert_eq!(p.sign(), -1);
+/// assert_eq!((&p * &p.inverse()), Permutation::identity(5));
+/// assert_eq!(p.to_string(), "(0 2 1)(3 4)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Permutation(Vec<usize>);
+
+impl Permutation {
+    /// Validate `v` as a permutation of `0..v.len()`.
+    pub fn new(v: Vec<usize>) -> Result<Self, PermutationError> {
+        let mut seen = vec![false; v.len()];
+        for &x in &v {
+            if x >= v.len() || std::mem::replace(&mut seen[x], true) {
+                return Err(PermutationError::NotAPermutation);
+            }
+        }
+        Ok(Permutation(v))
+    }
+
+    /// The identity permutation of `0..n`.
+    pub fn identity(n: usize) -> Self {
+        Permutation((0..n).collect())
+    }
+
+    /// Build a permutation of `0..n` from disjoint cycles.
+    ///
+    /// Elements not mentioned in any cycle are fixed points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::permutations::permutation::Permutation;
+    /// let p = Permutation::from_cycles(4, &[vec![0, 3]]).unwrap();
+    /// assert_eq!(p.as_slice(), &[3, 1, 2, 0]);
+    /// ```
+    pub fn from_cycles(n: usize, cycles: &[Vec<usize>]) -> Result<Self, PermutationError> {
+        let mut p: Vec<usize> = (0..n).collect();
+        let mut seen = vec![false; n];
+        for cycle in cycles {
+            for (i, &x) in cycle.iter().enumerate() {
+                if x >= n || std::mem::replace(&mut seen[x], true) {
+                    return Err(PermutationError::InvalidCycleNotation);
+                }
+                p[x] = cycle[(i + 1) % cycle.len()];
+            }
+        }
+        Ok(Permutation(p))
+    }
+
+    /// Parse cycle notation such as `"(0 2 1)(3 4)"` as a permutation of `0..n`.
+    ///
+    /// `"()"` and the empty string denote the identity.
+    pub fn from_cycle_notation(s: &str, n: usize) -> Result<Self, PermutationError> {
+        Permutation::from_cycles(n, &parse_cycles(s)?)
+    }
+
+    /// Rebuild a permutation from its Lehmer code.
+    pub fn from_lehmer_code(code: &[usize]) -> Result<Self, PermutationError> {
+        let n = code.len();
+        if code.iter().enumerate().any(|(i, &d)| d >= n - i) {
+            return Err(PermutationError::InvalidLehmerCode);
+        }
+        Ok(Permutation(from_lehmer_code(code)))
+    }
+
+    /// The Lehmer code (inversion table) of this permutation.
+    pub fn lehmer_code(&self) -> Vec<usize> {
+        lehmer_code(&self.0)
+    }
+
+    /// Number of elements `n`.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// `true` for the permutation of the empty set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// One-line notation.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// Consume into one-line notation.
+    pub fn into_vec(self) -> Vec<usize> {
+        self.0
+    }
+
+    /// The image of `i`.
+    pub fn apply(&self, i: usize) -> usize {
+        self.0[i]
+    }
+
+    /// Composition `self ∘ other`: apply `other` first, then `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths differ.
+    pub fn compose(&self, other: &Permutation) -> Permutation {
+        assert_eq!(self.len(), other.len(), "permutations must have equal length");
+        Permutation(other.0.iter().map(|&i| self.0[i]).collect())
+    }
+
+    /// The inverse permutation.
+    pub fn inverse(&self) -> Permutation {
+        let mut inv = vec![0; self.len()];
+        for (i, &x) in self.0.iter().enumerate() {
+            inv[x] = i;
+        }
+        Permutation(inv)
+    }
+
+    /// `self` composed with itself `e` times (`e < 0` uses the inverse).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusty_combinatorial::permutations::permutation::Permutation;
+    /// let p = Permutation::new(vec![1, 2, 0]).unwrap();
+    /// assert_eq!(p.pow(2), p.inverse());
+    /// assert_eq!(p.pow(-4), p.pow(2));
+    /// ```
+    pub fn pow(&self, e: i64) -> Permutation {
+        let mut base = if e < 0 { self.inverse() } else { self.clone() };
+        let mut e = e.unsigned_abs();
+        let mut acc = Permutation::identity(self.len());
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = acc.compose(&base);
+            }
+            base = base.compose(&base);
+            e >>= 1;
+        }
+        acc
+    }
+
+    /// All cycles, including fixed points, each starting at its smallest
+    /// element and ordered by that element.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut x = start;
+            while !seen[x] {
+                seen[x] = true;
+                cycle.push(x);
+                x = self.0[x];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// Number of cycles, counting fixed points.
+    pub fn num_cycles(&self) -> usize {
+        self.cycles().len()
+    }
+
+    /// Elements `i` with `p[i] == i`.
+    pub fn fixed_points(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&i| self.0[i] == i).collect()
+    }
+
+    /// Smallest `k ≥ 1` with `pᵏ = id`: the lcm of the cycle lengths.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the order does not fit in `usize`, which on 64-bit targets
+    /// first happens for `n = 358`.
+    pub fn order(&self) -> Option<usize> {
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+        self.cycles()
+            .iter()
+            .map(Vec::len)
+            .try_fold(1usize, |acc, len| (acc / gcd(acc, len)).checked_mul(len))
+    }
+
+    /// `+1` for even permutations, `-1` for odd ones.
+    pub fn sign(&self) -> i32 {
+        if self.is_even() { 1 } else { -1 }
+    }
+
+    /// `true` if the permutation is a product of an even number of transpositions.
+    pub fn is_even(&self) -> bool {
+        (self.len() - self.num_cycles()).is_multiple_of(2)
+    }
+}
+
+/// Parse `"(a b c)(d e)"` into its list of cycles.
+fn parse_cycles(s: &str) -> Result<Vec<Vec<usize>>, PermutationError> {
+    let mut cycles = Vec::new();
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let body = rest.strip_prefix('(').ok_or(PermutationError::InvalidCycleNotation)?;
+        let close = body.find(')').ok_or(PermutationError::InvalidCycleNotation)?;
+        let cycle = body[..close]
+            .split_whitespace()
+            .map(|t| t.parse::<usize>().map_err(|_| PermutationError::InvalidCycleNotation))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !cycle.is_empty() {
+            cycles.push(cycle);
+        }
+        rest = body[close + 1..].trim_start();
+    }
+    Ok(cycles)
+}
+
+impl FromStr for Permutation {
+    type Err = PermutationError;
+
+    /// Parse cycle notation; `n` is one more than the largest element mentioned.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cycles = parse_cycles(s)?;
+        let n = cycles.iter().flatten().max().map_or(0, |&m| m + 1);
+        Permutation::from_cycles(n, &cycles)
+    }
+}
+
+impl fmt::Display for Permutation {
+    /// Cycle notation without fixed points; the identity prints as `()`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut any = false;
+        for cycle in self.cycles().into_iter().filter(|c| c.len() > 1) {
+            any = true;
+            write!(f, "(")?;
+            for (i, x) in cycle.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", x)?;
+            }
+            write!(f, ")")?;
+        }
+        if !any {
+            write!(f, "()")?;
+        }
+        Ok(())
+    }
+}
+
+impl Mul for &Permutation {
+    type Output = Permutation;
+
+    /// `p * q` is [`Permutation::compose`]: apply `q` first.
+    fn mul(self, other: &Permutation) -> Permutation {
+        self.compose(other)
+    }
+}
+
+impl Index<usize> for Permutation {
+    type Output = usize;
+
+    fn index(&self, i: usize) -> &usize {
+        &self.0[i]
+    }
+}
+
+impl AsRef<[usize]> for Permutation {
+    fn as_ref(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = PermutationError;
+
+    fn try_from(v: Vec<usize>) -> Result<Self, Self::Error> {
+        Permutation::new(v)
+    }
+}
+
+impl From<Permutation> for Vec<usize> {
+    fn from(p: Permutation) -> Vec<usize> {
+        p.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutations::minimal::sjt_permutations;
+    use crate::permutations::ranking::{rank_perm, unrank_perm};
+
+    #[test]
+    fn test_group_laws() {
+        let perms: Vec<Permutation> = sjt_permutations(4)
+            .into_iter()
+            .map(|v| Permutation::new(v).unwrap())
+            .collect();
+        let id = Permutation::identity(4);
+        for p in &perms {
+            assert_eq!(&(p * &p.inverse()), &id);
+            assert_eq!(p.pow(p.order().unwrap() as i64), id);
+            for q in &perms {
+                assert_eq!((p * q).sign(), p.sign() * q.sign());
+            }
+            let round: Permutation = p.to_string().parse().unwrap();
+            assert_eq!(Permutation::from_cycle_notation(&p.to_string(), 4).unwrap(), *p);
+            assert!(round.len() <= 4);
+        }
+        // adjacent transpositions alternate the sign along the SJT order
+        assert!(perms.windows(2).all(|w| w[0].sign() == -w[1].sign()));
+    }
+
+    #[test]
+    fn test_order_overflow() {
+        // cycles of the 16 smallest primes: their product exceeds 2^64
+        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+        let mut start = 0;
+        let cycles: Vec<Vec<usize>> = primes
+            .iter()
+            .map(|&len| {
+                start += len;
+                (start - len..start).collect()
+            })
+            .collect();
+        let fits = Permutation::from_cycles(start - 53, &cycles[..15]).unwrap();
+        assert_eq!(fits.order(), Some(614_889_782_588_491_410));
+        assert_eq!(Permutation::from_cycles(start, &cycles).unwrap().order(), None);
+    }
+
+    #[test]
+    fn test_lehmer_code_matches_ranking() {
+        for r in 0..24 {
+            let p = Permutation::new(unrank_perm(r, 4)).unwrap();
+            let code = p.lehmer_code();
+            assert_eq!(Permutation::from_lehmer_code(&code), Ok(p.clone()));
+            assert_eq!(rank_perm(p.as_slice()), r);
+        }
+        assert_eq!(Permutation::from_lehmer_code(&[0, 2, 0]), Err(PermutationError::InvalidLehmerCode));
+    }
+
+    #[test]
+    fn test_cycle_notation_errors() {
+        assert_eq!(Permutation::new(vec![0, 0]), Err(PermutationError::NotAPermutation));
+        assert_eq!("(0 1)(1 2)".parse::<Permutation>(), Err(PermutationError::InvalidCycleNotation));
+        assert_eq!("(0 1".parse::<Permutation>(), Err(PermutationError::InvalidCycleNotation));
+        assert_eq!("()".parse::<Permutation>(), Ok(Permutation::identity(0)));
+        let p = Permutation::from_cycle_notation("(1 3)", 5).unwrap();
+        assert_eq!(p.fixed_points(), vec![0, 2, 4]);
+        assert_eq!(p.cycles(), vec![vec![0], vec![1, 3], vec![2], vec![4]]);
+    }
+}
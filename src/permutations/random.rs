@@ -11,11 +11,23 @@
 //!
 //! - Time: O(n)
 //! - Space: O(1) (in-place)
+//!
+//! ## Determinism
+//!
+//! Every sampler takes a caller-supplied `&mut impl Rng`; the `thread_rng`
+//! variants are thin convenience wrappers. For a fixed RNG state the output is
+//! a pure function of that state: `fisher_yates_with` draws exactly one
+//! `gen_range(0..=i)` for `i = n-1, …, 1`, in that order, and this draw
+//! sequence is part of the API, so seeded fixtures stay stable across releases
+//! of this crate. (Stability across `rand` releases is governed by the chosen
+//! RNG; `StdRng` may change between `rand` major versions.)
 
 use rand::Rng;
 
 /// Shuffle elements of a mutable slice in-place using Fisher–Yates.
 ///
+/// Uses `rand::thread_rng()`; see [`fisher_yates_with`] for a seedable variant.
+///
 /// # Arguments
 ///
 /// * `arr` — The mutable slice to shuffle.
@@ -29,7 +41,28 @@ use rand::Rng;
 /// assert_eq!(v.len(), 4);
 /// ```
 pub fn fisher_yates<T>(arr: &mut [T]) {
-    let mut rng = rand::thread_rng();
+    fisher_yates_with(arr, &mut rand::thread_rng());
+}
+
+/// Shuffle elements of a mutable slice in-place using the given RNG.
+///
+/// # Arguments
+///
+/// * `arr` — The mutable slice to shuffle.
+/// * `rng` — Source of randomness.
+///
+/// # Example
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use rusty_combinatorial::permutations::random::fisher_yates_with;
+/// let mut a = vec![1, 2, 3, 4, 5];
+/// let mut b = a.clone();
+/// fisher_yates_with(&mut a, &mut StdRng::seed_from_u64(7));
+/// fisher_yates_with(&mut b, &mut StdRng::seed_from_u64(7));
+/// assert_eq!(a, b);
+/// ```
+pub fn fisher_yates_with<T, R: Rng + ?Sized>(arr: &mut [T], rng: &mut R) {
     for i in (1..arr.len()).rev() {
         let j = rng.gen_range(0..=i);
         arr.swap(i, j);
@@ -39,6 +72,7 @@ pub fn fisher_yates<T>(arr: &mut [T]) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_fisher_yates_length() {
@@ -46,4 +80,11 @@ mod tests {
         fisher_yates(&mut v);
         assert_eq!(v.len(), 5);
     }
+
+    #[test]
+    fn test_fisher_yates_seeded_fixture() {
+        let mut v: Vec<usize> = (0..10).collect();
+        fisher_yates_with(&mut v, &mut StdRng::seed_from_u64(42));
+        assert_eq!(v, vec![7, 4, 1, 2, 8, 6, 9, 3, 0, 5]);
+    }
 }
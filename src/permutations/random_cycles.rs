@@ -0,0 +1,265 @@
+//! # Chapter 1: Permutations — Random Generation with Prescribed Cycle Structure
+//!
+//! Uniform samplers for permutations of `0..n` restricted by their cycles,
+//! sharing the RNG parameterisation of [`super::random`].
+//!
+//! ## Algorithm Description
+//!
+//! - **Cyclic permutations** (Sattolo): Fisher–Yates, but `j` is drawn from
+//!   `0..i` instead of `0..=i`, which always yields a single n-cycle.
+//! - **Cycle type**: shuffle `0..n`, then cut the shuffled sequence into cycles
+//!   of the requested lengths. Every permutation of that type arises from the
+//!   same number of shuffles, so the result is uniform.
+//! - **Derangements**: Fisher–Yates with early rejection: position `i` is final
+//!   once step `i` swaps it, so a fixed point there restarts the shuffle.
+//!   Expected `e ≈ 2.718` attempts.
+//! - **Involutions**: the largest unassigned element is a fixed point with
+//!   probability `t(m-1)/t(m)` (where `t(m) = t(m-1) + (m-1)·t(m-2)` counts
+//!   involutions of `m` elements), otherwise it is paired with a uniformly
+//!   chosen unassigned element.
+//!
+//! Permutations are returned in one-line notation: `p[i]` is the image of `i`.
+//!
+//! ## Complexity
+//!
+//! - Time: O(n) (expected O(n) for derangements)
+//! - Space: O(n)
+
+use rand::Rng;
+
+use super::random::fisher_yates_with;
+
+/// Shuffle a slice in-place into a uniformly random single cycle (Sattolo).
+///
+/// Uses `rand::thread_rng()`; see [`sattolo_with`] for a seedable variant.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::random_cycles::sattolo;
+/// let mut v = vec![0, 1, 2, 3];
+/// sattolo(&mut v);
+/// assert!(v.iter().enumerate().all(|(i, &x)| i != x));
+/// ```
+pub fn sattolo<T>(arr: &mut [T]) {
+    sattolo_with(arr, &mut rand::thread_rng());
+}
+
+/// Shuffle a slice in-place into a uniformly random single cycle using `rng`.
+///
+/// Draws exactly one `gen_range(0..i)` for `i = n-1, …, 1`.
+pub fn sattolo_with<T, R: Rng + ?Sized>(arr: &mut [T], rng: &mut R) {
+    for i in (1..arr.len()).rev() {
+        let j = rng.gen_range(0..i);
+        arr.swap(i, j);
+    }
+}
+
+/// Uniformly random cyclic permutation (one n-cycle) of `0..n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::random_cycles::random_cyclic_permutation;
+/// let p = random_cyclic_permutation(6);
+/// let mut x = 0;
+/// for _ in 0..5 {
+///     x = p[x];
+///     assert_ne!(x, 0);
+/// }
+/// assert_eq!(p[x], 0);
+/// ```
+pub fn random_cyclic_permutation(n: usize) -> Vec<usize> {
+    random_cyclic_permutation_with(n, &mut rand::thread_rng())
+}
+
+/// Uniformly random cyclic permutation of `0..n` using `rng`.
+///
+/// Draws exactly what [`sattolo_with`] draws on a slice of length `n`.
+pub fn random_cyclic_permutation_with<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    let mut p: Vec<usize> = (0..n).collect();
+    sattolo_with(&mut p, rng);
+    p
+}
+
+/// Uniformly random derangement (permutation without fixed points) of `0..n`.
+///
+/// # Returns
+///
+/// `None` for `n == 1`, which has no derangements.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::random_cycles::random_derangement;
+/// let d = random_derangement(5).unwrap();
+/// assert!(d.iter().enumerate().all(|(i, &x)| i != x));
+/// assert_eq!(random_derangement(1), None);
+/// ```
+pub fn random_derangement(n: usize) -> Option<Vec<usize>> {
+    random_derangement_with(n, &mut rand::thread_rng())
+}
+
+/// Uniformly random derangement of `0..n` using `rng`.
+///
+/// Each attempt draws `gen_range(0..=i)` for `i = n-1, …` down to the first
+/// fixed point it creates (or down to `1` if it creates none), and attempts
+/// repeat until one yields a derangement. Draws nothing for `n ≤ 1`.
+pub fn random_derangement_with<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Option<Vec<usize>> {
+    if n == 1 {
+        return None;
+    }
+    let mut p: Vec<usize> = (0..n).collect();
+    'attempt: loop {
+        p.iter_mut().enumerate().for_each(|(i, x)| *x = i);
+        for i in (1..n).rev() {
+            let j = rng.gen_range(0..=i);
+            p.swap(i, j);
+            if p[i] == i {
+                continue 'attempt;
+            }
+        }
+        if n == 0 || p[0] != 0 {
+            return Some(p);
+        }
+    }
+}
+
+/// Uniformly random involution (`p[p[i]] == i`) of `0..n`.
+///
+/// The fixed-point probabilities are evaluated in `f64`, so the distribution
+/// is uniform up to floating-point rounding.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::random_cycles::random_involution;
+/// let p = random_involution(7);
+/// assert!((0..7).all(|i| p[p[i]] == i));
+/// ```
+pub fn random_involution(n: usize) -> Vec<usize> {
+    random_involution_with(n, &mut rand::thread_rng())
+}
+
+/// Uniformly random involution of `0..n` using `rng`.
+///
+/// Takes the unassigned elements from largest down; for each, draws one
+/// `gen_bool` and, if the element is paired, one `gen_range(0..m-1)`, where
+/// `m` elements were unassigned.
+pub fn random_involution_with<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    // ratio[m] = t(m-1) / t(m), via 1 / ratio[m] = 1 + (m-1)·ratio[m-1]
+    let mut ratio = vec![1.0f64; n + 1];
+    for m in 2..=n {
+        ratio[m] = 1.0 / (1.0 + (m - 1) as f64 * ratio[m - 1]);
+    }
+
+    let mut p: Vec<usize> = (0..n).collect();
+    let mut free: Vec<usize> = (0..n).collect();
+    while let Some(x) = free.pop() {
+        let m = free.len() + 1;
+        if rng.gen_bool(ratio[m]) {
+            continue;
+        }
+        let y = free.swap_remove(rng.gen_range(0..free.len()));
+        p[x] = y;
+        p[y] = x;
+    }
+    p
+}
+
+/// Uniformly random permutation with the given cycle type.
+///
+/// # Arguments
+///
+/// * `cycle_type` — Cycle lengths (each ≥ 1, in any order); the permutation
+///   acts on `0..n` where `n` is their sum
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::random_cycles::random_of_cycle_type;
+/// let p = random_of_cycle_type(&[3, 2, 1]);
+/// assert_eq!(p.len(), 6);
+/// assert_eq!(p.iter().enumerate().filter(|&(i, &x)| i == x).count(), 1);
+/// ```
+pub fn random_of_cycle_type(cycle_type: &[usize]) -> Vec<usize> {
+    random_of_cycle_type_with(cycle_type, &mut rand::thread_rng())
+}
+
+/// Uniformly random permutation with the given cycle type using `rng`.
+///
+/// Draws exactly what [`fisher_yates_with`] draws on a slice of length `n`.
+///
+/// # Panics
+///
+/// Panics if any cycle length is zero.
+pub fn random_of_cycle_type_with<R: Rng + ?Sized>(cycle_type: &[usize], rng: &mut R) -> Vec<usize> {
+    assert!(cycle_type.iter().all(|&len| len > 0), "cycle lengths must be positive");
+    let n = cycle_type.iter().sum();
+    let mut order: Vec<usize> = (0..n).collect();
+    fisher_yates_with(&mut order, rng);
+
+    let mut p = vec![0; n];
+    let mut start = 0;
+    for &len in cycle_type {
+        let cycle = &order[start..start + len];
+        for i in 0..len {
+            p[cycle[i]] = cycle[(i + 1) % len];
+        }
+        start += len;
+    }
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn cycle_lengths(p: &[usize]) -> Vec<usize> {
+        let mut seen = vec![false; p.len()];
+        let mut lens = Vec::new();
+        for s in 0..p.len() {
+            let mut len = 0;
+            let mut x = s;
+            while !seen[x] {
+                seen[x] = true;
+                x = p[x];
+                len += 1;
+            }
+            if len > 0 {
+                lens.push(len);
+            }
+        }
+        lens.sort_unstable();
+        lens
+    }
+
+    #[test]
+    fn test_structured_samplers_respect_cycle_structure() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for n in 2..12 {
+            assert_eq!(cycle_lengths(&random_cyclic_permutation_with(n, &mut rng)), vec![n]);
+            let d = random_derangement_with(n, &mut rng).unwrap();
+            assert!(cycle_lengths(&d).iter().all(|&l| l >= 2));
+            let inv = random_involution_with(n, &mut rng);
+            assert!(cycle_lengths(&inv).iter().all(|&l| l <= 2));
+        }
+        let p = random_of_cycle_type_with(&[4, 1, 2, 2], &mut rng);
+        assert_eq!(cycle_lengths(&p), vec![1, 2, 2, 4]);
+        assert_eq!(random_derangement_with(0, &mut rng), Some(vec![]));
+    }
+
+    #[test]
+    fn test_derangements_are_uniform() {
+        // D(4) = 9 derangements, each should appear about 1/9 of the time.
+        use std::collections::HashMap;
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut counts: HashMap<Vec<usize>, usize> = HashMap::new();
+        for _ in 0..9000 {
+            *counts.entry(random_derangement_with(4, &mut rng).unwrap()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 9);
+        assert!(counts.values().all(|&c| (800..1200).contains(&c)));
+    }
+}
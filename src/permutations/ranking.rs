@@ -5,8 +5,8 @@
 //!
 //! ## Algorithm Description
 //!
-//! **Ranking**: For each position, count how many unused smaller values remain,
-//!           multiply by factorials (mixed‐radix).
+//! **Ranking**: For each position, count how many unused smaller values remain
+//!           (the Lehmer code), multiply by factorials (mixed‐radix).
 //! **Unranking**: Decompose the rank into mixed‐radix digits and pick elements
 //!             from a list of available values.
 //!
@@ -14,6 +14,17 @@
 //!
 //! - Time: O(n²)
 //! - Space: O(n)
+//!
+//! ## Arbitrary Precision
+//!
+//! `rank_perm` and `unrank_perm` work in `usize`, which only holds ranks up to
+//! `20!`. [`try_rank_perm`] and [`try_unrank_perm`] are generic over
+//! [`RankInt`] (`u64`, `u128` or [`BigUint`](crate::arith::biguint::BigUint)),
+//! using Horner's rule and repeated division by small radices so only
+//! word-sized multiplications and divisions are ever needed. They return a
+//! [`RankError`] instead of wrapping.
+
+use crate::arith::rank_int::{RankError, RankInt};
 
 /// Compute the lexicographic rank of permutation `p` (0‐based).
 ///
@@ -32,23 +43,19 @@
 /// let r = rank_perm(&[2,0,1]);
 /// assert_eq!(r, 4);
 /// ```
-/// Compute rank of permutation p (0-based), lexicographic order.
+///
+/// # Panics
+///
+/// Overflows `usize` for `n > 20` (a panic in debug builds); use
+/// [`try_rank_perm`] for longer permutations.
 pub fn rank_perm(p: &[usize]) -> usize {
     let n = p.len();
     let f = factorials(n);
-    let mut rank = 0usize;
-    let mut used = vec![false; n];
-    for i in 0..n {
-        let mut cnt = 0usize;
-        for j in 0..p[i] {
-            if !used[j] {
-                cnt += 1;
-            }
-        }
-        rank += cnt * f[n - 1 - i];
-        used[p[i]] = true;
-    }
-    rank
+    lehmer_code(p)
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| d * f[n - 1 - i])
+        .sum()
 }
 
 /// Reconstruct the permutation of length `n` with given `rank` (0‐based).
@@ -69,18 +76,130 @@ pub fn rank_perm(p: &[usize]) -> usize {
 /// let p = unrank_perm(4, 3);
 /// assert_eq!(p, vec![2,0,1]);
 /// ```
-/// Reconstruct permutation of length n with given rank (0-based)
 pub fn unrank_perm(mut rank: usize, n: usize) -> Vec<usize> {
     let f = factorials(n);
-    let mut avail: Vec<usize> = (0..n).collect();
-    let mut out = Vec::with_capacity(n);
+    let mut code = Vec::with_capacity(n);
     for i in 0..n {
         let block = f[n - 1 - i];
-        let idx = rank / block;
+        code.push(rank / block);
         rank %= block;
-        out.push(avail.remove(idx));
     }
-    out
+    from_lehmer_code(&code)
+}
+
+/// Compute the Lehmer code (inversion table) of permutation `p`.
+///
+/// Digit `i` counts the entries after position `i` that are smaller than
+/// `p[i]`, so it lies in `0..n-i`. The rank of `p` is this code read as a
+/// factorial-base number.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::ranking::lehmer_code;
+/// assert_eq!(lehmer_code(&[2, 0, 1]), vec![2, 0, 0]);
+/// ```
+pub fn lehmer_code(p: &[usize]) -> Vec<usize> {
+    let mut used = vec![false; p.len()];
+    p.iter()
+        .map(|&x| {
+            let cnt = used[..x].iter().filter(|&&u| !u).count();
+            used[x] = true;
+            cnt
+        })
+        .collect()
+}
+
+/// Rebuild the permutation whose Lehmer code is `code`.
+///
+/// # Panics
+///
+/// Panics if some digit `code[i]` is not in `0..n-i`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::ranking::from_lehmer_code;
+/// assert_eq!(from_lehmer_code(&[2, 0, 0]), vec![2, 0, 1]);
+/// ```
+pub fn from_lehmer_code(code: &[usize]) -> Vec<usize> {
+    let mut avail: Vec<usize> = (0..code.len()).collect();
+    code.iter().map(|&d| avail.remove(d)).collect()
+}
+
+/// Compute the lexicographic rank of permutation `p` in any [`RankInt`] type.
+///
+/// # Arguments
+///
+/// * `p` — A slice of a permutation of `0..n`
+///
+/// # Returns
+///
+/// The 0-based rank, `Err(RankError::Overflow)` if it does not fit in `R`, or
+/// `Err(RankError::InvalidInput)` if `p` is not a permutation of `0..n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::arith::biguint::BigUint;
+/// use rusty_combinatorial::arith::rank_int::RankError;
+/// use rusty_combinatorial::permutations::ranking::try_rank_perm;
+/// let last: Vec<usize> = (0..30).rev().collect();
+/// assert_eq!(try_rank_perm::<u64>(&last), Err(RankError::Overflow));
+/// let r: BigUint = try_rank_perm(&last).unwrap();
+/// assert_eq!(r.to_string(), "265252859812191058636308479999999"); // 30! - 1
+/// ```
+pub fn try_rank_perm<R: RankInt>(p: &[usize]) -> Result<R, RankError> {
+    let n = p.len();
+    let mut seen = vec![false; n];
+    for &x in p {
+        if x >= n || std::mem::replace(&mut seen[x], true) {
+            return Err(RankError::InvalidInput);
+        }
+    }
+    let mut rank = R::zero();
+    for (i, d) in lehmer_code(p).into_iter().enumerate() {
+        // Horner step in the factorial number system: radix n - i
+        rank = rank
+            .checked_mul_usize(n - i)
+            .and_then(|r| r.checked_add_usize(d))
+            .ok_or(RankError::Overflow)?;
+    }
+    Ok(rank)
+}
+
+/// Reconstruct the permutation of length `n` with a [`RankInt`] rank.
+///
+/// # Arguments
+///
+/// * `rank` — Rank in `0..n!`
+/// * `n` — Permutation length
+///
+/// # Returns
+///
+/// The permutation, or `Err(RankError::OutOfRange)` if `rank ≥ n!`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::ranking::{try_rank_perm, try_unrank_perm};
+/// let p: Vec<usize> = (0..25).rev().collect();
+/// let r: u128 = try_rank_perm(&p).unwrap();
+/// assert_eq!(try_unrank_perm(&r, 25), Ok(p));
+/// ```
+pub fn try_unrank_perm<R: RankInt>(rank: &R, n: usize) -> Result<Vec<usize>, RankError> {
+    // Factorial-base digits, least significant (radix 1) first.
+    let mut digits = vec![0usize; n];
+    let mut rest = rank.clone();
+    for r in 1..=n {
+        let (q, d) = rest.div_rem_usize(r);
+        digits[n - r] = d;
+        rest = q;
+    }
+    if !rest.is_zero() {
+        return Err(RankError::OutOfRange);
+    }
+    Ok(from_lehmer_code(&digits))
 }
 
 /// compute factorials up to n
@@ -103,4 +222,26 @@ mod tests {
         let q = unrank_perm(r, 3);
         assert_eq!(p, q);
     }
+
+    #[test]
+    fn test_try_rank_matches_usize_rank() {
+        use crate::arith::biguint::BigUint;
+        for r in 0..120 {
+            let p = unrank_perm(r, 5);
+            assert_eq!(try_rank_perm::<u64>(&p), Ok(r as u64));
+            let big: BigUint = try_rank_perm(&p).unwrap();
+            assert_eq!(try_unrank_perm(&big, 5), Ok(p));
+        }
+        assert_eq!(try_unrank_perm(&120u64, 5), Err(RankError::OutOfRange));
+        assert_eq!(try_rank_perm::<u64>(&[0, 0]), Err(RankError::InvalidInput));
+    }
+
+    #[test]
+    fn test_try_rank_big_roundtrip() {
+        use crate::arith::biguint::BigUint;
+        let p: Vec<usize> = (0..60).map(|i| (i * 7) % 60).collect();
+        let r: BigUint = try_rank_perm(&p).unwrap();
+        assert_eq!(try_rank_perm::<u128>(&p), Err(RankError::Overflow));
+        assert_eq!(try_unrank_perm(&r, 60), Ok(p));
+    }
 }
@@ -0,0 +1,364 @@
+//! # Chapter 1: Permutations — Star Transpositions (Ehrlich's Algorithm)
+//!
+//! Generates all permutations of `0..n` so that each one is obtained from the
+//! previous by swapping the **first** entry with some other entry — a walk on
+//! the Cayley graph generated by the "star" transpositions `(0 j)`.
+//!
+//! ## Algorithm Description
+//!
+//! Ehrlich's method (Knuth, TAOCP 7.2.1.2, Algorithm E). The digit scan and
+//! the reversal below take O(k) time for step `k`, which averages to O(1):
+//!
+//! - Keep a mixed-radix counter `c[1..=n]` and an auxiliary permutation `b`.
+//! - Find the smallest `k` with `c[k] < k` (resetting smaller digits), stop if
+//!   `k = n`, otherwise increment `c[k]`.
+//! - Swap positions `0` and `b[k]`, then reverse `b[1..k]`.
+//!
+//! ## Ranking
+//!
+//! As in [Heap's order](super::heap), `c` holds the factorial-base digits of
+//! the step count, and between two increments of `c[k]` the lower digits make
+//! one full pass. That pass swaps position `0` with positions read from
+//! `b[1..k]`, so it is the pass started from `b = identity`, relabelled
+//! through the current `b`; its effect on both the permutation and on
+//! `b[1..k]` is precomputed once per `k`. [`unrank_star_perm`] replays `c[k]`
+//! passes per digit, and [`rank_star_perm`] recovers each `c[k]` from the
+//! entry at position `b[k]`, which the lower passes never touch. The replay
+//! also restores `b` and `c`, so [`StarPermutations::range`] and
+//! [`Iterator::nth`] can resume generation from any rank.
+//! [`try_rank_star_perm`] ranks in any [`RankInt`] type and reports overflow
+//! instead of wrapping.
+//!
+//! ## Complexity
+//!
+//! - Time: O(1) amortized per permutation
+//! - Rank / unrank: O(n³)
+//! - Space: O(n)
+
+use std::ops::Range;
+
+use super::cursor::{factorial, is_permutation, seek_nth, Cursor, Seekable};
+use crate::arith::rank_int::{RankError, RankInt};
+
+/// Lazy generator of permutations of `0..n` by star transpositions.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::star::StarPermutations;
+/// let swaps: Vec<usize> = StarPermutations::new(3).swaps().collect();
+/// assert_eq!(swaps, vec![1, 2, 1, 2, 1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StarPermutations {
+    perm: Vec<usize>,
+    b: Vec<usize>,
+    c: Vec<usize>,
+    cursor: Cursor,
+}
+
+impl StarPermutations {
+    /// Start at the identity permutation of `0..n`.
+    pub fn new(n: usize) -> Self {
+        StarPermutations {
+            perm: (0..n).collect(),
+            b: (0..n).collect(),
+            c: vec![0; n + 1],
+            cursor: Cursor::new(factorial(n)),
+        }
+    }
+
+    /// Iterate over the permutations of `0..n` whose ranks in star-transposition order lie in
+    /// `ranks`, starting from an unranked permutation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ranks.start > ranks.end`, if `ranks.end > n!`, or if `n!`
+    /// does not fit in `usize`.
+    pub fn range(n: usize, ranks: Range<usize>) -> Self {
+        let start = ranks.start;
+        let mut it = StarPermutations::new(n);
+        it.cursor = Cursor::range(factorial(n), ranks);
+        it.seek(start);
+        it
+    }
+
+    /// The current permutation.
+    pub fn current(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Advance by one star transposition.
+    ///
+    /// # Returns
+    ///
+    /// `Some(j)` if positions `0` and `j` were exchanged, or `None` once all
+    /// `n!` permutations have been visited.
+    pub fn next_swap(&mut self) -> Option<usize> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if self.cursor.start() {
+            // the starting permutation counts as visited
+            self.cursor.advance();
+            if self.cursor.at_end() {
+                return None;
+            }
+        }
+        let n = self.perm.len();
+        let mut k = 1;
+        while k < n && self.c[k] == k {
+            self.c[k] = 0;
+            k += 1;
+        }
+        if k >= n {
+            self.cursor.finish();
+            return None;
+        }
+        self.c[k] += 1;
+        let j = self.b[k];
+        self.perm.swap(0, j);
+        self.b[1..k].reverse();
+        self.cursor.advance();
+        Some(j)
+    }
+
+    /// Consume the generator, yielding only the positions swapped with `0`.
+    pub fn swaps(mut self) -> impl Iterator<Item = usize> {
+        std::iter::from_fn(move || self.next_swap())
+    }
+}
+
+impl Iterator for StarPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.cursor.exhausted() {
+            return None;
+        }
+        if self.cursor.start() {
+            self.cursor.advance();
+            return Some(self.perm.clone());
+        }
+        self.next_swap().map(|_| self.perm.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    fn nth(&mut self, m: usize) -> Option<Vec<usize>> {
+        seek_nth(self, m)
+    }
+}
+
+impl Seekable for StarPermutations {
+    fn cursor(&mut self) -> &mut Cursor {
+        &mut self.cursor
+    }
+
+    fn seek(&mut self, rank: usize) {
+        self.cursor.seek(rank);
+        if !self.cursor.at_end() {
+            (self.perm, self.b, self.c) = star_state(rank, self.perm.len());
+        }
+    }
+}
+
+/// Rank of `p` among the permutations generated by star transpositions.
+///
+/// # Panics
+///
+/// Panics if `p` is not a permutation of `0..n`, or if the rank does not fit
+/// in `usize` (possible for `n > 20`); see [`try_rank_star_perm`].
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::star::rank_star_perm;
+/// assert_eq!(rank_star_perm(&[0, 1, 2]), 0);
+/// assert_eq!(rank_star_perm(&[2, 0, 1]), 2);
+/// ```
+pub fn rank_star_perm(p: &[usize]) -> usize {
+    match try_rank_star_perm(p) {
+        Ok(rank) => rank,
+        Err(RankError::InvalidInput) => panic!("not a permutation of 0..{}", p.len()),
+        Err(e) => panic!("star rank overflows usize: {e:?}"),
+    }
+}
+
+/// Rank of `p` in star-transposition order in any [`RankInt`] type.
+///
+/// # Returns
+///
+/// The rank, `Err(RankError::Overflow)` if it does not fit in `R`, or
+/// `Err(RankError::InvalidInput)` if `p` is not a permutation of `0..n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::arith::rank_int::RankError;
+/// use rusty_combinatorial::permutations::star::try_rank_star_perm;
+/// assert_eq!(try_rank_star_perm::<u64>(&[2, 0, 1]), Ok(2));
+/// assert_eq!(try_rank_star_perm::<u64>(&[0, 0]), Err(RankError::InvalidInput));
+/// ```
+pub fn try_rank_star_perm<R: RankInt>(p: &[usize]) -> Result<R, RankError> {
+    if !is_permutation(p) {
+        return Err(RankError::InvalidInput);
+    }
+    let n = p.len();
+    let passes = star_passes(n);
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut b: Vec<usize> = (0..n).collect();
+    let mut rank = R::zero();
+    for k in (1..n).rev() {
+        let mut c = 0;
+        while perm[b[k]] != p[b[k]] {
+            star_digit_step(&mut perm, &mut b, &passes[k], k);
+            c += 1;
+        }
+        rank = rank
+            .checked_mul_usize(k + 1)
+            .and_then(|r| r.checked_add_usize(c))
+            .ok_or(RankError::Overflow)?;
+    }
+    Ok(rank)
+}
+
+/// The permutation of `0..n` with the given rank in star-transposition order.
+///
+/// # Panics
+///
+/// Panics if `rank >= n!`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::star::unrank_star_perm;
+/// assert_eq!(unrank_star_perm(2, 3), vec![2, 0, 1]);
+/// ```
+pub fn unrank_star_perm(rank: usize, n: usize) -> Vec<usize> {
+    star_state(rank, n).0
+}
+
+/// Permutation, `b` and counter `c` after `rank` steps.
+fn star_state(mut rank: usize, n: usize) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+    let mut c = vec![0; n + 1];
+    for (k, d) in c.iter_mut().enumerate().take(n).skip(1) {
+        *d = rank % (k + 1);
+        rank /= k + 1;
+    }
+    assert!(rank == 0, "rank out of range for n = {}", n);
+    let passes = star_passes(n);
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut b: Vec<usize> = (0..n).collect();
+    for k in (1..n).rev() {
+        for _ in 0..c[k] {
+            star_digit_step(&mut perm, &mut b, &passes[k], k);
+        }
+    }
+    (perm, b, c)
+}
+
+/// Effect of a full pass of the digits below `k`, started from the identity
+/// permutation and `b = identity`: position `x < k` ends up holding what
+/// position `perm[x]` held, and `b[x]` what `b[flip[x]]` held.
+#[derive(Debug, Clone)]
+struct StarPass {
+    perm: Vec<usize>,
+    flip: Vec<usize>,
+}
+
+/// One full pass of the digits below `k`, then one increment of `c[k]`.
+fn star_digit_step(perm: &mut [usize], b: &mut [usize], pass: &StarPass, k: usize) {
+    apply_star_pass(perm, b, pass);
+    perm.swap(0, b[k]);
+    b[1..k].reverse();
+}
+
+/// Apply `pass` from the current `b`.
+fn apply_star_pass(perm: &mut [usize], b: &mut [usize], pass: &StarPass) {
+    // the pass swaps positions b[x] where the identity pass swaps x
+    let (p0, b0) = (perm.to_vec(), b.to_vec());
+    for (x, (&from, &flip)) in pass.perm.iter().zip(&pass.flip).enumerate() {
+        perm[b0[x]] = p0[b0[from]];
+        b[x] = b0[flip];
+    }
+}
+
+/// `passes[k]` for every `k < n`.
+fn star_passes(n: usize) -> Vec<StarPass> {
+    let mut passes = vec![
+        StarPass { perm: Vec::new(), flip: Vec::new() },
+        StarPass { perm: vec![0], flip: vec![0] },
+    ];
+    for k in 1..n.saturating_sub(1) {
+        let mut perm: Vec<usize> = (0..=k).collect();
+        let mut b: Vec<usize> = (0..=k).collect();
+        for _ in 0..k {
+            star_digit_step(&mut perm, &mut b, &passes[k], k);
+        }
+        apply_star_pass(&mut perm, &mut b, &passes[k]);
+        passes.push(StarPass { perm, flip: b });
+    }
+    passes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_star_visits_all_with_star_swaps() {
+        for n in 0..=7 {
+            let perms: Vec<Vec<usize>> = StarPermutations::new(n).collect();
+            let distinct: HashSet<&Vec<usize>> = perms.iter().collect();
+            assert_eq!(distinct.len(), (1..=n).product::<usize>());
+            for w in perms.windows(2) {
+                let diff: Vec<usize> = (0..n).filter(|&i| w[0][i] != w[1][i]).collect();
+                assert_eq!(diff.len(), 2);
+                assert_eq!(diff[0], 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_star_rank_unrank() {
+        for n in 0..=6 {
+            for (r, p) in StarPermutations::new(n).enumerate() {
+                assert_eq!(rank_star_perm(&p), r);
+                assert_eq!(unrank_star_perm(r, n), p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_star_try_rank_checks_overflow() {
+        let p: Vec<usize> = (0..21).rev().collect();
+        let wide: u128 = try_rank_star_perm(&p).unwrap();
+        assert!(wide > u64::MAX as u128 && wide < (1..=21u128).product());
+        assert_eq!(try_rank_star_perm::<u64>(&p), Err(RankError::Overflow));
+        for (r, p) in StarPermutations::new(5).enumerate() {
+            assert_eq!(try_rank_star_perm::<u128>(&p), Ok(r as u128));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn test_star_rank_rejects_non_permutation() {
+        rank_star_perm(&[0, 2, 2]);
+    }
+
+    #[test]
+    fn test_star_seek_resumes_generation() {
+        let all: Vec<Vec<usize>> = StarPermutations::new(5).collect();
+        for start in (0..=120).step_by(7) {
+            let shard: Vec<Vec<usize>> = StarPermutations::range(5, start..120).collect();
+            assert_eq!(shard, all[start..]);
+        }
+        let mut it = StarPermutations::new(5);
+        assert_eq!(it.nth(50), Some(all[50].clone()));
+        assert_eq!(it.next(), Some(all[51].clone()));
+    }
+}
@@ -0,0 +1,332 @@
+//! # Chapter 1: Permutations — Permutation Statistics
+//!
+//! Classical statistics of a sequence `p[0..n]`, together with their
+//! distribution tables over all `n!` permutations.
+//!
+//! ## Definitions
+//!
+//! - **Inversion**: a pair `i < j` with `p[i] > p[j]`.
+//! - **Descent**: a position `i` with `p[i] > p[i+1]`; the **major index** is the
+//!   sum of the 1-based descent positions `i + 1`.
+//! - **Exceedance**: a position `i` with `p[i] > i` (permutations of `0..n`).
+//! - **Peak**: an interior position `i` with `p[i-1] < p[i] > p[i+1]`.
+//!
+//! ## Algorithm Description
+//!
+//! - Inversions are counted during a merge sort: when an element of the right
+//!   half is emitted, every element still waiting in the left half is larger.
+//! - The longest increasing subsequence uses patience sorting: `tails[l]` holds
+//!   the smallest possible tail of an increasing subsequence of length `l + 1`,
+//!   located by binary search; back-pointers recover the subsequence.
+//! - Eulerian numbers `A(n, k)` (permutations with `k` descents) satisfy
+//!   `A(n, k) = (k+1)·A(n-1, k) + (n-k)·A(n-1, k-1)`.
+//! - Mahonian numbers (permutations with `k` inversions, equivalently major
+//!   index `k`) are the coefficients of `∏ᵢ (1 + q + … + q^(i-1))`.
+//!
+//! ## Complexity
+//!
+//! - Inversions, LIS: O(n log n)
+//! - Descents, major index, cycles, exceedances, peaks: O(n)
+//! - Eulerian row: O(n²); Mahonian row: O(n³)
+
+/// Number of inversions of `p`, in O(n log n).
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::inversions;
+/// assert_eq!(inversions(&[2, 0, 3, 1]), 3);
+/// ```
+pub fn inversions<T: Ord + Clone>(p: &[T]) -> usize {
+    fn sort_count<T: Ord + Clone>(v: &mut [T], buf: &mut Vec<T>) -> usize {
+        let n = v.len();
+        if n < 2 {
+            return 0;
+        }
+        let mid = n / 2;
+        let mut count = sort_count(&mut v[..mid], buf) + sort_count(&mut v[mid..], buf);
+        buf.clear();
+        let (mut i, mut j) = (0, mid);
+        while i < mid && j < n {
+            if v[j] < v[i] {
+                count += mid - i;
+                buf.push(v[j].clone());
+                j += 1;
+            } else {
+                buf.push(v[i].clone());
+                i += 1;
+            }
+        }
+        buf.extend_from_slice(&v[i..mid]);
+        buf.extend_from_slice(&v[j..]);
+        v.clone_from_slice(buf);
+        count
+    }
+
+    let mut v = p.to_vec();
+    sort_count(&mut v, &mut Vec::with_capacity(p.len()))
+}
+
+/// Positions `i` (0-based) with `p[i] > p[i+1]`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::descent_set;
+/// assert_eq!(descent_set(&[3, 1, 2, 0]), vec![0, 2]);
+/// ```
+pub fn descent_set<T: Ord>(p: &[T]) -> Vec<usize> {
+    (0..p.len().saturating_sub(1)).filter(|&i| p[i] > p[i + 1]).collect()
+}
+
+/// Number of descents of `p`.
+pub fn descents<T: Ord>(p: &[T]) -> usize {
+    p.windows(2).filter(|w| w[0] > w[1]).count()
+}
+
+/// Major index: the sum of the 1-based descent positions.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::major_index;
+/// assert_eq!(major_index(&[3, 1, 2, 0]), 1 + 3);
+/// ```
+pub fn major_index<T: Ord>(p: &[T]) -> usize {
+    descent_set(p).iter().map(|&i| i + 1).sum()
+}
+
+/// Number of cycles (fixed points included) of a permutation of `0..n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::num_cycles;
+/// assert_eq!(num_cycles(&[1, 0, 2, 4, 3]), 3);
+/// ```
+pub fn num_cycles(p: &[usize]) -> usize {
+    let mut seen = vec![false; p.len()];
+    let mut cycles = 0;
+    for start in 0..p.len() {
+        if seen[start] {
+            continue;
+        }
+        cycles += 1;
+        let mut x = start;
+        while !seen[x] {
+            seen[x] = true;
+            x = p[x];
+        }
+    }
+    cycles
+}
+
+/// A longest strictly increasing subsequence of `p`, in O(n log n).
+///
+/// Among subsequences of maximal length, the one returned ends with the
+/// smallest possible final value.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::longest_increasing_subsequence;
+/// let lis = longest_increasing_subsequence(&[3, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(lis, vec![1, 4, 5, 6]);
+/// ```
+pub fn longest_increasing_subsequence<T: Ord + Clone>(p: &[T]) -> Vec<T> {
+    // tails[l] = index of the smallest tail of an increasing run of length l+1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev = vec![usize::MAX; p.len()];
+    for i in 0..p.len() {
+        let l = tails.partition_point(|&t| p[t] < p[i]);
+        if l > 0 {
+            prev[i] = tails[l - 1];
+        }
+        if l == tails.len() {
+            tails.push(i);
+        } else {
+            tails[l] = i;
+        }
+    }
+
+    let mut out = Vec::with_capacity(tails.len());
+    let mut cur = tails.last().copied().unwrap_or(usize::MAX);
+    while cur != usize::MAX {
+        out.push(p[cur].clone());
+        cur = prev[cur];
+    }
+    out.reverse();
+    out
+}
+
+/// Number of exceedances `p[i] > i` of a permutation of `0..n`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::exceedances;
+/// assert_eq!(exceedances(&[2, 0, 3, 1]), 2);
+/// ```
+pub fn exceedances(p: &[usize]) -> usize {
+    p.iter().enumerate().filter(|&(i, &x)| x > i).count()
+}
+
+/// Interior positions `i` with `p[i-1] < p[i] > p[i+1]`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::peak_set;
+/// assert_eq!(peak_set(&[0, 3, 1, 4, 2]), vec![1, 3]);
+/// ```
+pub fn peak_set<T: Ord>(p: &[T]) -> Vec<usize> {
+    (1..p.len().saturating_sub(1))
+        .filter(|&i| p[i - 1] < p[i] && p[i] > p[i + 1])
+        .collect()
+}
+
+/// Number of peaks of `p`.
+pub fn peaks<T: Ord>(p: &[T]) -> usize {
+    p.windows(3).filter(|w| w[0] < w[1] && w[1] > w[2]).count()
+}
+
+/// Eulerian numbers `A(n, k)`: permutations of `n` elements with `k` descents.
+///
+/// # Returns
+///
+/// The row `[A(n, 0), …, A(n, n-1)]` (`[1]` for `n = 0`), or `None` if an
+/// entry does not fit in `usize`, which on 64-bit targets first happens for
+/// `n = 22`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::eulerian_numbers;
+/// assert_eq!(eulerian_numbers(4), Some(vec![1, 11, 11, 1]));
+/// assert_eq!(eulerian_numbers(22), None);
+/// ```
+pub fn eulerian_numbers(n: usize) -> Option<Vec<usize>> {
+    let mut row = vec![1usize];
+    for m in 2..=n {
+        let mut next = vec![0usize; m];
+        for k in 0..m {
+            let stay = if k < row.len() { (k + 1).checked_mul(row[k])? } else { 0 };
+            let grow = if k > 0 { (m - k).checked_mul(row[k - 1])? } else { 0 };
+            next[k] = stay.checked_add(grow)?;
+        }
+        row = next;
+    }
+    Some(row)
+}
+
+/// Mahonian numbers: permutations of `n` elements with `k` inversions.
+///
+/// By MacMahon's theorem this is also the distribution of the major index.
+///
+/// # Returns
+///
+/// The row indexed by `k = 0..=n(n-1)/2`, or `None` if an entry does not fit
+/// in `usize`, which on 64-bit targets first happens for `n = 22`.
+///
+/// # Example
+///
+/// ```
+/// use rusty_combinatorial::permutations::stats::mahonian_numbers;
+/// assert_eq!(mahonian_numbers(3), Some(vec![1, 2, 2, 1]));
+/// assert_eq!(mahonian_numbers(22), None);
+/// ```
+pub fn mahonian_numbers(n: usize) -> Option<Vec<usize>> {
+    let mut row = vec![1usize];
+    for i in 2..=n {
+        // multiply by 1 + q + … + q^(i-1): each coefficient is the sum of a
+        // sliding window of i entries of the previous row
+        let mut next = Vec::with_capacity(row.len() + i - 1);
+        let mut window = 0usize;
+        for k in 0..row.len() + i - 1 {
+            // drop the outgoing entry first, so the sum overflows only if
+            // the coefficient itself does
+            if k >= i {
+                window -= row[k - i];
+            }
+            if k < row.len() {
+                window = window.checked_add(row[k])?;
+            }
+            next.push(window);
+        }
+        row = next;
+    }
+    Some(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutations::lex::next_lex_perm;
+
+    #[test]
+    fn test_tables_match_enumeration() {
+        for n in 1..=6 {
+            let mut by_des = vec![0usize; n];
+            let mut by_inv = vec![0usize; n * (n - 1) / 2 + 1];
+            let mut by_maj = by_inv.clone();
+            let mut p: Vec<usize> = (0..n).collect();
+            loop {
+                by_des[descents(&p)] += 1;
+                by_inv[inversions(&p)] += 1;
+                by_maj[major_index(&p)] += 1;
+                if !next_lex_perm(&mut p) {
+                    break;
+                }
+            }
+            assert_eq!(Some(by_des), eulerian_numbers(n));
+            assert_eq!(Some(by_inv), mahonian_numbers(n));
+            assert_eq!(Some(by_maj), mahonian_numbers(n));
+        }
+    }
+
+    #[test]
+    fn test_tables_report_overflow() {
+        // rows sum to n!; for n = 21 that exceeds 2^64 but every entry fits
+        for n in [20, 21] {
+            let total: u128 = (1..=n as u128).product();
+            let sum = |row: Vec<usize>| row.iter().map(|&x| x as u128).sum::<u128>();
+            assert_eq!(eulerian_numbers(n).map(sum), Some(total));
+            assert_eq!(mahonian_numbers(n).map(sum), Some(total));
+        }
+        assert_eq!(eulerian_numbers(22), None);
+        assert_eq!(mahonian_numbers(22), None);
+    }
+
+    #[test]
+    fn test_statistics_against_brute_force() {
+        let mut p: Vec<usize> = (0..7).collect();
+        let mut exc = vec![0usize; 7];
+        loop {
+            let brute_inv = (0..7)
+                .flat_map(|i| (i + 1..7).map(move |j| (i, j)))
+                .filter(|&(i, j)| p[i] > p[j])
+                .count();
+            assert_eq!(inversions(&p), brute_inv);
+            let mut best = [1usize; 7];
+            for j in 0..7 {
+                for i in 0..j {
+                    if p[i] < p[j] {
+                        best[j] = best[j].max(best[i] + 1);
+                    }
+                }
+            }
+            let lis = longest_increasing_subsequence(&p);
+            assert!(lis.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(lis.len(), *best.iter().max().unwrap());
+            exc[exceedances(&p)] += 1;
+            if !next_lex_perm(&mut p) {
+                break;
+            }
+        }
+        // exceedances are Eulerian-distributed, like descents
+        assert_eq!(Some(exc), eulerian_numbers(7));
+        assert_eq!(num_cycles(&[0, 1, 2]), 3);
+        assert_eq!(peaks(&[1, 3, 2, 5, 4]), 2);
+        assert_eq!(inversions(&['c', 'a', 'b', 'a']), 4);
+    }
+}
@@ -16,7 +16,8 @@
 ///
 /// # Arguments
 ///
-/// * `shape` — Slice of row lengths, e.g. `[3, 2]`
+/// * `shape` — Slice of row lengths, e.g. `[3, 2]`; a
+///   [`Partition`](crate::partitions::partition::Partition) can be passed as `&p`
 ///
 /// # Returns
 ///